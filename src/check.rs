@@ -0,0 +1,98 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Component, Path, PathBuf},
+};
+
+use similar::TextDiff;
+
+use crate::error::Error;
+
+/// The first line of the TypeScript generated, which tells the files
+/// `--check` may find orphaned.
+pub const HEADER: &str = "// Generated by spider-jockey, do not edit.\n";
+
+/// Reads a file, which may be missing.
+pub fn read_existing(path: &Path) -> Result<Option<String>, Error> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(source) => Err(Error::Io {
+            file: path.into(),
+            source,
+        }),
+    }
+}
+
+/// A unified diff from the file on disk to the one generated, either of
+/// which may be missing.
+pub fn diff(path: &Path, before: Option<&str>, after: Option<&str>) -> String {
+    let name = path.display().to_string();
+    let header = |contents: Option<&str>| match contents {
+        Some(_) => name.as_str(),
+        None => "/dev/null",
+    };
+    TextDiff::from_lines(before.unwrap_or(""), after.unwrap_or(""))
+        .unified_diff()
+        .header(header(before), header(after))
+        .to_string()
+}
+
+/// Leaves out the `.` of a path, so that `./Token.ts` is `Token.ts`.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| *component != Component::CurDir)
+        .collect()
+}
+
+/// Finds the TypeScript generated below a directory, the working one when
+/// empty, that is no longer expected, leaving out hidden directories and
+/// `node_modules`.
+pub fn orphans(dir: &Path, expected: &[PathBuf]) -> Result<Vec<PathBuf>, Error> {
+    let expected: Vec<_> = expected.iter().map(|path| normalize(path)).collect();
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    find_orphans(dir, &expected)
+}
+
+fn find_orphans(dir: &Path, expected: &[PathBuf]) -> Result<Vec<PathBuf>, Error> {
+    let mut found = vec![];
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(found),
+        Err(source) => {
+            return Err(Error::Io {
+                file: dir.into(),
+                source,
+            })
+        }
+    };
+    let mut paths = vec![];
+    for entry in entries {
+        let entry = entry.map_err(|source| Error::Io {
+            file: dir.into(),
+            source,
+        })?;
+        paths.push(normalize(&entry.path()));
+    }
+    paths.sort();
+    for path in paths {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "node_modules" {
+                found.extend(find_orphans(&path, expected)?);
+            }
+        } else if name.ends_with(".ts") && !expected.contains(&path) {
+            let generated = fs::read(&path)
+                .map(|contents| contents.starts_with(HEADER.as_bytes()))
+                .unwrap_or(false);
+            if generated {
+                found.push(path);
+            }
+        }
+    }
+    Ok(found)
+}
@@ -0,0 +1,446 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{
+    code_emitter::{Case, CodeEmitter, NumericMapping, Target},
+    config::{Config, Filter},
+    error::Error,
+    parser::{signature, AbiEntry, Contract},
+};
+
+pub const USAGE: &str = "\
+Generates TypeScript bindings from contract ABIs and artifacts.
+
+Usage: spider-jockey [OPTIONS] <INPUTS>...
+
+Inputs are artifacts or ABIs, or glob patterns like `artifacts/**/*.json`.
+
+Options:
+  -o, --out-dir <DIR>      Where to write the bindings [default: .]
+      --root <DIR>         Keeps the directories of the inputs below DIR
+  -t, --target <TARGET>    generic, ethers-v5 or ethers-v6 [default: generic]
+      --numeric <MAPPING>  bigint, BigNumberish or Name:module [default: bigint]
+  -c, --config <FILE>      Reads the options from a TOML file, instead of the
+                           spider-jockey.toml or .spiderjockeyrc found up from
+                           the working directory
+      --no-config          Reads no configuration file
+      --name <NAME>        Names the contract of the next input
+      --contract <NAME>    Only emits the contract, as Name or source:Name
+      --bundle <FILE>      Writes every contract into a single module FILE
+      --index              Writes an index.ts re-exporting every module
+      --human-readable     Writes the ABIs as signatures instead of code
+      --keep-going         Carries on with the next inputs when one fails
+  -w, --watch              Regenerates the outputs of the inputs as they change
+      --check              Writes nothing, printing a diff of the outputs that
+                           are stale, missing or orphaned, and fails if any
+  -q, --quiet              Only prints errors
+  -v, --verbose            Also prints the shared files written
+  -h, --help               Prints this help
+  -V, --version            Prints the version
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    Quiet,
+    #[default]
+    Normal,
+    Verbose,
+}
+
+/// The settings of a contract that replace the project ones.
+#[derive(Default)]
+pub struct ContractOptions {
+    pub numeric: Option<NumericMapping>,
+    pub files: Option<Case>,
+    pub methods: Option<Case>,
+    pub functions: Option<Filter>,
+}
+
+/// What to do, as told by the command line and the configuration.
+#[derive(Default)]
+pub struct Options {
+    pub emitter: CodeEmitter,
+    /// The input files, along with the name given to their contract.
+    pub inputs: Vec<(String, Option<String>)>,
+    /// The inputs as given, before expanding their glob patterns.
+    pub patterns: Vec<(String, Option<String>)>,
+    /// The contracts emitted, by `Name` or `source:Name`.
+    pub contracts: Filter,
+    /// The functions wrapped, by name or signature.
+    pub functions: Filter,
+    /// How the files written for contracts are named.
+    pub files: Case,
+    pub overrides: BTreeMap<String, ContractOptions>,
+    pub out_dir: PathBuf,
+    /// Where the directories of the inputs are kept from, if they are.
+    pub root: Option<PathBuf>,
+    pub verbosity: Verbosity,
+    /// The module every contract is written into, instead of one each.
+    pub bundle: Option<PathBuf>,
+    pub index: bool,
+    pub human_readable: bool,
+    pub keep_going: bool,
+    /// Regenerates the outputs of the inputs as they change.
+    pub watch: bool,
+    /// Compares the outputs against the disk instead of writing them.
+    pub check: bool,
+}
+
+pub enum Command {
+    Run(Box<Options>),
+    Help,
+    Version,
+}
+
+impl Options {
+    fn overrides_of(&self, contract: &Contract) -> Option<&ContractOptions> {
+        contract
+            .source_name
+            .as_ref()
+            .and_then(|source| self.overrides.get(&format!("{}:{}", source, contract.name)))
+            .or_else(|| self.overrides.get(&contract.name))
+    }
+
+    /// Leaves out the contracts and functions not asked for.
+    pub fn select(&self, mut contracts: Vec<Contract>) -> Vec<Contract> {
+        contracts.retain(|contract| {
+            let qualified = contract
+                .source_name
+                .as_ref()
+                .map(|source| format!("{}:{}", source, contract.name));
+            let mut names = vec![contract.name.as_str()];
+            names.extend(qualified.as_deref());
+            self.contracts.allows(&names)
+        });
+        for contract in contracts.iter_mut() {
+            let functions = self
+                .overrides_of(contract)
+                .and_then(|overrides| overrides.functions.as_ref())
+                .unwrap_or(&self.functions);
+            contract.abi.retain(|entry| match entry {
+                AbiEntry::Function { name, inputs, .. } => {
+                    functions.allows(&[name, &signature(name, inputs)])
+                }
+                _ => true,
+            });
+        }
+        contracts
+    }
+
+    /// The emitter of a contract, as overridden for it.
+    pub fn emitter_of(&self, contract: &Contract) -> CodeEmitter {
+        let mut emitter = self.emitter.clone();
+        if let Some(overrides) = self.overrides_of(contract) {
+            if let Some(numeric) = &overrides.numeric {
+                emitter.numeric = numeric.clone();
+            }
+            if let Some(methods) = overrides.methods {
+                emitter.methods = methods;
+            }
+        }
+        emitter
+    }
+
+    /// The name of the files written for a contract, without extension.
+    pub fn file_name_of(&self, contract: &Contract) -> String {
+        self.overrides_of(contract)
+            .and_then(|overrides| overrides.files)
+            .unwrap_or(self.files)
+            .apply(&contract.name)
+    }
+
+    /// The directory the outputs of an input go to.
+    pub fn out_dir_of(&self, input: &str) -> Result<PathBuf, Error> {
+        let Some(root) = &self.root else {
+            return Ok(self.out_dir.clone());
+        };
+        let dir = Path::new(input).parent().unwrap_or(Path::new(""));
+        let relative = normalize(dir)
+            .strip_prefix(normalize(root))
+            .map(Path::to_path_buf)
+            .map_err(|_| {
+                Error::Usage(format!(
+                    "`{}` isn't below the root `{}`",
+                    input,
+                    root.display()
+                ))
+            })?;
+        Ok(self.out_dir.join(relative))
+    }
+
+    /// How the outputs of an input import the abstraction, which is written
+    /// at the top of the output directory.
+    pub fn abstraction_import_of(&self, input: &str) -> Result<String, Error> {
+        let depth = self
+            .out_dir_of(input)?
+            .strip_prefix(&self.out_dir)
+            .map_or(0, |relative| relative.components().count());
+        Ok(if depth == 0 {
+            "./AbstractContract".into()
+        } else {
+            format!("{}AbstractContract", "../".repeat(depth))
+        })
+    }
+}
+
+/// Drops the `.` components of a path, so `./a` is below `.`.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
+
+fn is_pattern(input: &str) -> bool {
+    input.contains(['*', '?', '['])
+}
+
+/// Expands the glob patterns among inputs into the files they match, in
+/// order. Strictly, a pattern matching nothing is an error.
+pub fn expand_inputs(
+    inputs: &[(String, Option<String>)],
+    strict: bool,
+) -> Result<Vec<(String, Option<String>)>, Error> {
+    let mut expanded = vec![];
+    for (input, name) in inputs {
+        if !is_pattern(input) {
+            expanded.push((input.clone(), name.clone()));
+            continue;
+        }
+        let files = expand(input)?;
+        if strict && files.is_empty() {
+            return Err(Error::Usage(format!("`{}` matches no file", input)));
+        }
+        expanded.extend(files.into_iter().map(|file| (file, None)));
+    }
+    Ok(expanded)
+}
+
+/// Expands a glob pattern into the files it matches, in order.
+fn expand(pattern: &str) -> Result<Vec<String>, Error> {
+    let paths = glob::glob(pattern)
+        .map_err(|err| Error::Usage(format!("bad pattern `{}`: {}", pattern, err)))?;
+    let mut files = vec![];
+    for path in paths {
+        let path = path.map_err(|err| Error::Io {
+            file: err.path().into(),
+            source: err.into(),
+        })?;
+        if path.is_file() {
+            files.push(path.to_string_lossy().into_owned());
+        }
+    }
+    Ok(files)
+}
+
+pub fn parse_args<I>(args: I) -> Result<Command, Error>
+where
+    I: Iterator<Item = String>,
+{
+    let mut options = Options::default();
+    let mut out_dir = None;
+    let mut root = None;
+    let mut target = None;
+    let mut numeric = None;
+    let mut config = None;
+    let mut no_config = false;
+    let mut contracts = vec![];
+    let mut inputs = vec![];
+    let mut name = None;
+    // `--flag=value` is the same as `--flag value`.
+    let mut args = args
+        .flat_map(|arg| match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => vec![flag.into(), value.into()],
+            _ => vec![arg],
+        })
+        .collect::<Vec<_>>()
+        .into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| Error::Usage(format!("missing the value of `{}`", arg)))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "-o" | "--out-dir" => out_dir = Some(PathBuf::from(value()?)),
+            "--root" => root = Some(PathBuf::from(value()?)),
+            "-t" | "--target" => target = Some(value()?),
+            "--numeric" => numeric = Some(value()?),
+            "-c" | "--config" => config = Some(PathBuf::from(value()?)),
+            "--no-config" => no_config = true,
+            // Names the contract of the next input, if it doesn't name it.
+            "--name" => name = Some(value()?),
+            // Picks contracts out of the inputs holding many of them.
+            "--contract" => contracts.push(value()?),
+            "--bundle" => options.bundle = Some(PathBuf::from(value()?)),
+            // Writes a barrel re-exporting the modules of every contract.
+            "--index" => options.index = true,
+            // Writes the ABIs as signatures, instead of emitting code.
+            "--human-readable" => options.human_readable = true,
+            // Carries on with the next inputs when one fails.
+            "--keep-going" => options.keep_going = true,
+            "-w" | "--watch" => options.watch = true,
+            // Fails on outputs differing from the disk, for CI.
+            "--check" => options.check = true,
+            "-q" | "--quiet" => options.verbosity = Verbosity::Quiet,
+            "-v" | "--verbose" => options.verbosity = Verbosity::Verbose,
+            flag if flag.starts_with('-') && flag.len() > 1 => {
+                return Err(Error::Usage(format!("unknown option `{}`", flag)))
+            }
+            _ => inputs.push((arg, name.take())),
+        }
+    }
+    let modes = [
+        ("--bundle", options.bundle.is_some()),
+        ("--index", options.index),
+        ("--human-readable", options.human_readable),
+    ];
+    let modes: Vec<_> = modes.iter().filter(|(_, on)| *on).collect();
+    if let [(first, _), (second, _), ..] = modes[..] {
+        return Err(Error::Usage(format!(
+            "`{}` and `{}` don't go together",
+            first, second
+        )));
+    }
+    if options.check && options.watch {
+        return Err(Error::Usage(
+            "`--check` and `--watch` don't go together".to_owned(),
+        ));
+    }
+    if config.is_none() && !no_config {
+        config = env::current_dir()
+            .ok()
+            .and_then(|dir| Config::discover(&dir));
+    }
+    let config = match config {
+        Some(path) => {
+            let contents = fs::read_to_string(&path).map_err(|source| Error::Io {
+                file: path.clone(),
+                source,
+            })?;
+            Config::read(&path, &contents)?
+        }
+        None => Config::default(),
+    };
+    if inputs.is_empty() {
+        inputs = config
+            .inputs
+            .into_iter()
+            .map(|input| (input, None))
+            .collect();
+    }
+    if inputs.is_empty() {
+        return Err(Error::Usage(
+            "no inputs given, see `--help` for the usage".into(),
+        ));
+    }
+    if let Some((input, _)) = inputs
+        .iter()
+        .find(|(input, name)| is_pattern(input) && name.is_some())
+    {
+        return Err(Error::Usage(format!(
+            "`--name` can't name the many files of `{}`",
+            input
+        )));
+    }
+    options.inputs = expand_inputs(&inputs, true)?;
+    options.patterns = inputs;
+    options.out_dir = out_dir.or(config.out_dir).unwrap_or_default();
+    options.root = root.or(config.root);
+    // The mode of the command line replaces the one of the configuration.
+    if modes.is_empty() {
+        options.bundle = config.bundle;
+        options.index = config.index;
+    }
+    options.keep_going |= config.keep_going;
+    options.contracts = config.contracts;
+    if !contracts.is_empty() {
+        options.contracts.include = contracts;
+    }
+    options.functions = config.functions;
+    if let Some(files) = config.naming.files {
+        options.files = files.parse().map_err(Error::Usage)?;
+    }
+    if let Some(methods) = config.naming.methods {
+        options.emitter.methods = methods.parse().map_err(Error::Usage)?;
+    }
+    for (contract, overrides) in config.overrides {
+        options.overrides.insert(
+            contract,
+            ContractOptions {
+                numeric: overrides
+                    .numeric
+                    .map(|numeric| numeric.parse())
+                    .transpose()
+                    .map_err(Error::Usage)?,
+                files: overrides
+                    .naming
+                    .files
+                    .map(|files| files.parse())
+                    .transpose()
+                    .map_err(Error::Usage)?,
+                methods: overrides
+                    .naming
+                    .methods
+                    .map(|methods| methods.parse())
+                    .transpose()
+                    .map_err(Error::Usage)?,
+                functions: overrides.functions,
+            },
+        );
+    }
+    // Bundles translate every contract with the same emitter.
+    if options.bundle.is_some() {
+        if let Some((contract, _)) = options
+            .overrides
+            .iter()
+            .find(|(_, overrides)| overrides.numeric.is_some() || overrides.methods.is_some())
+        {
+            return Err(Error::Usage(format!(
+                "`--bundle` can't apply the numeric mapping and method naming of `overrides.{}`, which only go with a file per contract",
+                contract
+            )));
+        }
+    }
+    if let Some(target) = target.or(config.target) {
+        options.emitter.target = target.parse().map_err(Error::Usage)?;
+    }
+    if let Some(numeric) = numeric.or(config.numeric) {
+        options.emitter.numeric = numeric.parse().map_err(Error::Usage)?;
+    }
+    // ethers v6 hands out `bigint`s, which no class is converted from.
+    if let (Target::EthersV6, NumericMapping::Class { name, .. }) =
+        (options.emitter.target, &options.emitter.numeric)
+    {
+        return Err(Error::Usage(format!(
+            "`ethers-v6` decodes integers as `bigint`, which can't be typed as `{}`",
+            name
+        )));
+    }
+    // The ethers runtimes, shared by every contract, decode integers into
+    // the type of the project mapping only.
+    if options.emitter.target != Target::Generic {
+        let decoded = |numeric: &NumericMapping| match numeric {
+            NumericMapping::Class { name, .. } => name.clone(),
+            _ => "bigint".to_owned(),
+        };
+        let shared = decoded(&options.emitter.numeric);
+        for (contract, overrides) in options.overrides.iter() {
+            let Some(numeric) = &overrides.numeric else {
+                continue;
+            };
+            if decoded(numeric) != shared {
+                return Err(Error::Usage(format!(
+                    "`overrides.{}` types integers as `{}`, but the ethers runtime shared by every contract decodes them as `{}`",
+                    contract,
+                    decoded(numeric),
+                    shared
+                )));
+            }
+        }
+    }
+    Ok(Command::Run(Box::new(options)))
+}
@@ -1,87 +1,1861 @@
+use std::str::FromStr;
+
 use crate::{
-    parser::{AbiEntry, Contract, DataType},
+    error::Error,
+    parser::{
+        event_signature, signature, AbiEntry, Contract, CtorInput, DataType, EventInput, FuncIO,
+        LinkReference, StateMutability,
+    },
     ts,
 };
 
-fn translate_type(io_type: DataType) -> ts::Type {
-    match io_type {
-        DataType::UInt256 => ts::Type::Number,
-        DataType::UInt8 => ts::Type::Number,
-        DataType::UInt8Array => ts::Type::Array(Box::new(ts::Type::Number)),
-        DataType::UInt256Array => ts::Type::Array(Box::new(ts::Type::Number)),
-        DataType::String => ts::Type::String,
-        DataType::Address => ts::Type::String,
-        DataType::Bool => ts::Type::Boolean,
-        DataType::Contract(_) => todo!(),
-        DataType::Enum(_) => todo!(),
-        DataType::Other(_) => todo!(),
+/// How integers wider than 48 bits, which a javascript `number` can't hold
+/// without losing precision, are typed.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum NumericMapping {
+    /// Native `bigint`s.
+    #[default]
+    BigInt,
+    /// ethers' `BigNumberish` for inputs and `bigint` for outputs.
+    BigNumberish,
+    /// A class imported from a module, like `BigNumber` from `ethers`.
+    Class { name: String, module: String },
+}
+impl FromStr for NumericMapping {
+    type Err = String;
+
+    /// Parses `bigint`, `BigNumberish`, or a class as `Name:module`.
+    fn from_str(strategy: &str) -> Result<Self, Self::Err> {
+        match strategy {
+            "bigint" => Ok(NumericMapping::BigInt),
+            "BigNumberish" => Ok(NumericMapping::BigNumberish),
+            other => match other.split_once(':') {
+                Some((name, module)) if !name.is_empty() && !module.is_empty() => {
+                    Ok(NumericMapping::Class {
+                        name: name.into(),
+                        module: module.into(),
+                    })
+                }
+                _ => Err(format!("unknown numeric mapping `{}`", other)),
+            },
+        }
+    }
+}
+impl NumericMapping {
+    /// Inputs accept anything that can be converted to the wide integer,
+    /// while outputs are always of the narrowest type.
+    fn wide(&self, direction: Direction) -> ts::Type {
+        let loose = || vec![ts::Type::BigInt, ts::Type::Number, ts::Type::String];
+        match (self, direction) {
+            (NumericMapping::BigInt | NumericMapping::BigNumberish, Direction::Output) => {
+                ts::Type::BigInt
+            }
+            (NumericMapping::BigInt, Direction::Input) => ts::Type::Union(loose()),
+            (NumericMapping::BigNumberish, Direction::Input) => {
+                ts::Type::Class("BigNumberish".into())
+            }
+            (NumericMapping::Class { name, .. }, Direction::Output) => {
+                ts::Type::Class(name.clone())
+            }
+            (NumericMapping::Class { name, .. }, Direction::Input) => {
+                let mut types = vec![ts::Type::Class(name.clone())];
+                types.extend(loose());
+                ts::Type::Union(types)
+            }
+        }
+    }
+    /// The name to import, and the module it comes from, when a wide integer
+    /// is typed in the given direction.
+    fn import(&self, direction: Direction) -> Option<(&str, &str)> {
+        match (self, direction) {
+            (NumericMapping::BigNumberish, Direction::Input) => Some(("BigNumberish", "ethers")),
+            (NumericMapping::Class { name, module }, _) => Some((name, module)),
+            _ => None,
+        }
+    }
+}
+
+/// The library the emitted code runs on top of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Target {
+    /// Only the `AbstractContract` interface, implemented by the user.
+    #[default]
+    Generic,
+    /// Along with an implementation over ethers v5 contracts.
+    EthersV5,
+    /// Along with an implementation over ethers v6 contracts.
+    EthersV6,
+}
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(target: &str) -> Result<Self, Self::Err> {
+        match target {
+            "generic" => Ok(Target::Generic),
+            "ethers-v5" => Ok(Target::EthersV5),
+            "ethers-v6" => Ok(Target::EthersV6),
+            other => Err(format!(
+                "unknown target `{}`, expected `generic`, `ethers-v5` or `ethers-v6`",
+                other
+            )),
+        }
+    }
+}
+
+/// How names are cased when turned into code or files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Case {
+    /// As the contract has it.
+    #[default]
+    Preserve,
+    Camel,
+    Pascal,
+    Snake,
+    Kebab,
+}
+impl FromStr for Case {
+    type Err = String;
+
+    fn from_str(case: &str) -> Result<Self, Self::Err> {
+        match case {
+            "preserve" => Ok(Case::Preserve),
+            "camelCase" => Ok(Case::Camel),
+            "PascalCase" => Ok(Case::Pascal),
+            "snake_case" => Ok(Case::Snake),
+            "kebab-case" => Ok(Case::Kebab),
+            other => Err(format!(
+                "unknown case `{}`, expected `preserve`, `camelCase`, `PascalCase`, `snake_case` or `kebab-case`",
+                other
+            )),
+        }
+    }
+}
+impl Case {
+    /// Splits a name into its words, like `ERC20Token` into `ERC20` and
+    /// `Token`, or `get_dy` into `get` and `dy`.
+    fn words(name: &str) -> Vec<String> {
+        let chars: Vec<char> = name.chars().collect();
+        let mut words = vec![];
+        let mut word = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            if c == '_' || c == '-' {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+                continue;
+            }
+            let previous = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            // `aB` and `1B` start a word, and so does the last capital of
+            // `ABc`.
+            let starts = c.is_uppercase()
+                && previous.is_some_and(|previous| {
+                    previous.is_lowercase()
+                        || previous.is_ascii_digit()
+                        || previous.is_uppercase() && next.is_some_and(|next| next.is_lowercase())
+                });
+            if starts && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            word.push(c);
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+        words
+    }
+    /// Cases a name, keeping its leading underscores.
+    pub fn apply(&self, name: &str) -> String {
+        let capitalize = |word: &str| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| {
+                    first
+                        .to_uppercase()
+                        .chain(chars.flat_map(char::to_lowercase))
+                })
+                .into_iter()
+                .flatten()
+                .collect::<String>()
+        };
+        let body = name.trim_start_matches('_');
+        let prefix = &name[..name.len() - body.len()];
+        let words = Self::words(body);
+        let body = match self {
+            Case::Preserve => body.to_owned(),
+            Case::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i == 0 {
+                        word.to_lowercase()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+            Case::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+            Case::Snake => words
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Case::Kebab => words
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("-"),
+        };
+        format!("{}{}", prefix, body)
+    }
+}
+
+/// Emits a statement that the builder has no words for.
+fn statement(builder: ts::Script, code: &str) -> ts::Script {
+    builder.expression().field(code).expression_end()
+}
+
+/// Emits the statements of a body, one per line.
+fn statements(builder: ts::Script, code: &[&str]) -> ts::Script {
+    code.iter()
+        .fold(builder, |builder, code| statement(builder, code))
+}
+
+/// Whether a type is accepted by the emitted code or returned from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Input,
+    Output,
+}
+
+/// A struct that gets its own interface in the emitted code.
+struct Struct {
+    qualified_name: String,
+    direction: Direction,
+    name: String,
+    fields: Vec<(String, ts::Type)>,
+}
+
+/// Translates parameter types, collecting the structs they refer to.
+struct Types {
+    numeric: NumericMapping,
+    target: Target,
+    structs: Vec<Struct>,
+    /// The numeric import, once a wide integer needs it.
+    numeric_import: Option<(String, String)>,
+}
+impl Types {
+    fn new(numeric: NumericMapping, target: Target) -> Self {
+        Types {
+            numeric,
+            target,
+            structs: vec![],
+            numeric_import: None,
+        }
+    }
+    fn translate_elementary(&mut self, io_type: &DataType, direction: Direction) -> ts::Type {
+        match io_type {
+            DataType::UInt(bits) | DataType::Int(bits) if *bits > 48 => {
+                if let Some((name, module)) = self.numeric.import(direction) {
+                    self.numeric_import = Some((name.to_owned(), module.to_owned()));
+                }
+                self.numeric.wide(direction)
+            }
+            // ethers v6 decodes every integer as a `bigint`, however narrow.
+            DataType::UInt(_) | DataType::Int(_) | DataType::Enum(_)
+                if direction == Direction::Output && self.target == Target::EthersV6 =>
+            {
+                ts::Type::BigInt
+            }
+            DataType::UInt(_) | DataType::Int(_) => ts::Type::Number,
+            DataType::Enum(_) => ts::Type::Number,
+            DataType::Bool => ts::Type::Boolean,
+            // Addresses, byte arrays and function pointers travel as hex
+            // strings, and fixed point numbers as their decimal representation.
+            DataType::Address
+            | DataType::Contract(_)
+            | DataType::String
+            | DataType::Bytes
+            | DataType::FixedBytes(_)
+            | DataType::Function
+            | DataType::Fixed(_, _)
+            | DataType::UFixed(_, _) => ts::Type::String,
+            DataType::Array(inner, Some(size)) => {
+                ts::Type::Tuple(vec![self.translate_elementary(inner, direction); *size])
+            }
+            DataType::Array(inner, None) => {
+                ts::Type::Array(Box::new(self.translate_elementary(inner, direction)))
+            }
+            DataType::Tuple | DataType::Struct(_) | DataType::Other(_) => ts::Type::Unknown,
+        }
+    }
+    /// Translates a parameter, using its internal type to name the tuples
+    /// that come from structs.
+    fn translate(
+        &mut self,
+        io_type: &DataType,
+        internal_type: &DataType,
+        components: &[FuncIO],
+        direction: Direction,
+    ) -> ts::Type {
+        match io_type {
+            DataType::Array(inner, size) => {
+                let internal_inner = match internal_type {
+                    DataType::Array(internal_inner, _) => internal_inner,
+                    other => other,
+                };
+                let inner = self.translate(inner, internal_inner, components, direction);
+                match size {
+                    Some(size) => ts::Type::Tuple(vec![inner; *size]),
+                    None => ts::Type::Array(Box::new(inner)),
+                }
+            }
+            DataType::Tuple => {
+                let fields = self.fields(components, direction);
+                if fields.iter().any(|(name, _)| name.is_empty()) {
+                    ts::Type::Tuple(fields.into_iter().map(|(_, kind)| kind).collect())
+                } else if let DataType::Struct(qualified_name) = internal_type {
+                    ts::Type::Class(self.register(qualified_name, components, direction))
+                } else {
+                    ts::Type::Interface(fields)
+                }
+            }
+            other => self.translate_elementary(other, direction),
+        }
+    }
+    fn fields(&mut self, components: &[FuncIO], direction: Direction) -> Vec<(String, ts::Type)> {
+        components
+            .iter()
+            .map(|io| {
+                let kind =
+                    self.translate(&io.io_type, &io.internal_type, &io.components, direction);
+                (io.name.clone(), kind)
+            })
+            .collect()
+    }
+    /// Registers a struct if it wasn't seen before, returning the name of
+    /// its interface. Structs get a separate `Output` interface when their
+    /// outputs are typed narrower than their inputs.
+    fn register(
+        &mut self,
+        qualified_name: &str,
+        components: &[FuncIO],
+        direction: Direction,
+    ) -> String {
+        if !self
+            .structs
+            .iter()
+            .any(|known| known.qualified_name == qualified_name)
+        {
+            let input = self.fields(components, Direction::Input);
+            let output = self.fields(components, Direction::Output);
+            let short_name = qualified_name.rsplit('.').next().unwrap_or(qualified_name);
+            // Structs declared in different contracts may share a name.
+            let name = if self.structs.iter().any(|known| known.name == short_name) {
+                qualified_name.replace('.', "_")
+            } else {
+                short_name.to_owned()
+            };
+            let differs = output != input;
+            self.structs.push(Struct {
+                qualified_name: qualified_name.to_owned(),
+                direction: Direction::Input,
+                name: name.clone(),
+                fields: input,
+            });
+            if differs {
+                self.structs.push(Struct {
+                    qualified_name: qualified_name.to_owned(),
+                    direction: Direction::Output,
+                    name: format!("{}Output", name),
+                    fields: output,
+                });
+            }
+        }
+        self.structs
+            .iter()
+            .filter(|known| known.qualified_name == qualified_name)
+            .find(|known| known.direction == direction)
+            .or_else(|| {
+                self.structs
+                    .iter()
+                    .find(|known| known.qualified_name == qualified_name)
+            })
+            .map(|known| known.name.clone())
+            .unwrap_or_default()
+    }
+    /// Translates the outputs of a function into the type it resolves to:
+    /// nothing, a single value, or several values as an object when all of
+    /// them are named and as a tuple otherwise.
+    fn returns(&mut self, outputs: &[FuncIO]) -> ts::Type {
+        let mut values = self.fields(outputs, Direction::Output);
+        match values.len() {
+            0 => ts::Type::Void,
+            1 => values.remove(0).1,
+            _ if values.iter().all(|(name, _)| !name.is_empty()) => ts::Type::Interface(values),
+            _ => ts::Type::Tuple(values.into_iter().map(|(_, kind)| kind).collect()),
+        }
+    }
+    /// Translates the inputs of an event into the fields of its arguments.
+    fn event_fields(&mut self, inputs: &[EventInput]) -> Vec<EventField> {
+        param_names(inputs.iter().map(|io| &io.name))
+            .into_iter()
+            .zip(inputs)
+            .map(|(name, io)| EventField {
+                name,
+                kind: self.translate(
+                    &io.input_type,
+                    &io.internal_type,
+                    &io.components,
+                    Direction::Output,
+                ),
+                filter_kind: self.translate(
+                    &io.input_type,
+                    &io.internal_type,
+                    &io.components,
+                    Direction::Input,
+                ),
+                indexed: io.indexed,
+            })
+            .collect()
+    }
+    /// Translates a list of inputs, naming the unnamed ones.
+    fn params(&mut self, inputs: &[FuncIO], direction: Direction) -> Vec<(String, ts::Type)> {
+        param_names(inputs.iter().map(|io| &io.name))
+            .into_iter()
+            .zip(inputs)
+            .map(|(name, io)| {
+                let kind =
+                    self.translate(&io.io_type, &io.internal_type, &io.components, direction);
+                (name, kind)
+            })
+            .collect()
+    }
+    /// Translates the inputs of a constructor, naming the unnamed ones.
+    fn ctor_params(&mut self, inputs: &[CtorInput]) -> Vec<(String, ts::Type)> {
+        param_names(inputs.iter().map(|io| &io.name))
+            .into_iter()
+            .zip(inputs)
+            .map(|(name, io)| {
+                let kind = self.translate(
+                    &io.input_type,
+                    &io.internal_type,
+                    &io.components,
+                    Direction::Input,
+                );
+                (name, kind)
+            })
+            .collect()
     }
 }
 
+/// Names the parameters, giving a positional name to the unnamed ones.
+fn param_names<'a>(names: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut i = -1;
+    names
+        .map(|name| {
+            if name.is_empty() {
+                i += 1;
+                format!("_param{}", i)
+            } else {
+                name.clone()
+            }
+        })
+        .collect()
+}
+
+/// Renames the parameters clashing with names the emitted code declares
+/// next to them, appending underscores.
+fn avoid(params: Vec<(String, ts::Type)>, taken: &[&str]) -> Vec<(String, ts::Type)> {
+    let mut names: Vec<String> = params.iter().map(|(name, _)| name.clone()).collect();
+    for i in 0..names.len() {
+        if !taken.contains(&names[i].as_str()) {
+            continue;
+        }
+        while taken.contains(&names[i].as_str())
+            || names
+                .iter()
+                .enumerate()
+                .any(|(j, other)| j != i && *other == names[i])
+        {
+            names[i].push('_');
+        }
+    }
+    names
+        .into_iter()
+        .zip(params)
+        .map(|(name, (_, kind))| (name, kind))
+        .collect()
+}
+
+/// A contract function, translated to the signature of its wrapper method.
+struct FunctionSignature {
+    name: String,
+    params: Vec<(String, ts::Type)>,
+    returns: ts::Type,
+    mutability: StateMutability,
+}
+impl FunctionSignature {
+    /// Whether the function only reads the chain, in which case its value
+    /// can be returned directly instead of a transaction.
+    fn reads(&self) -> bool {
+        matches!(
+            self.mutability,
+            StateMutability::View | StateMutability::Pure
+        )
+    }
+    /// The type of the trailing `overrides` parameter of functions that
+    /// send transactions.
+    fn overrides(&self) -> Option<&'static str> {
+        match self.mutability {
+            StateMutability::View | StateMutability::Pure => None,
+            StateMutability::NonPayable => Some("Overrides"),
+            StateMutability::Payable => Some("PayableOverrides"),
+        }
+    }
+    /// The type the wrapper method resolves to.
+    fn resolves_to(&self) -> ts::Type {
+        if self.reads() {
+            self.returns.clone()
+        } else {
+            ts::Type::Class("ContractTransaction".into())
+        }
+    }
+}
+
+/// An argument of an event, typed both as received by listeners and as
+/// matched by query filters.
+struct EventField {
+    name: String,
+    kind: ts::Type,
+    filter_kind: ts::Type,
+    indexed: bool,
+}
+
+/// A contract event, translated to the interface of its arguments.
+struct EventSignature {
+    name: String,
+    /// What the contract knows the event by: its name, or its signature when
+    /// overloaded.
+    target: String,
+    /// The name of the interface holding the arguments of the event.
+    interface: String,
+    fields: Vec<EventField>,
+}
+impl EventSignature {
+    /// The name of the method dealing with the event, like `onTransfer`,
+    /// or `"onTransfer(address,uint256)"` for an overload.
+    fn method(&self, prefix: &str) -> String {
+        if self.target == self.name {
+            format!("{}{}", prefix, self.name)
+        } else {
+            format!("\"{}{}\"", prefix, self.target)
+        }
+    }
+    /// The filter accepted when querying past events, which can only match
+    /// the indexed fields.
+    fn filter(&self) -> ts::Type {
+        ts::Type::Partial(Box::new(ts::Type::Interface(
+            self.fields
+                .iter()
+                .filter(|field| field.indexed)
+                .map(|field| (field.name.clone(), field.filter_kind.clone()))
+                .collect(),
+        )))
+    }
+}
+
+/// A custom error, along with the class it is thrown as.
+struct ErrorSignature {
+    name: String,
+    class: String,
+    params: Vec<(String, ts::Type)>,
+}
+
+/// A contract translated into everything its bindings declare.
+struct Bindings<'a> {
+    name: &'a str,
+    functions: Vec<(String, FunctionSignature)>,
+    events: Vec<EventSignature>,
+    errors: Vec<ErrorSignature>,
+    deployment: Option<(&'a str, FunctionSignature)>,
+    /// The libraries the bytecode is linked against, as `source:Name`,
+    /// along with where their addresses go.
+    libraries: Vec<(String, &'a [LinkReference])>,
+    receive: Option<FunctionSignature>,
+    fallback: Option<FunctionSignature>,
+}
+impl Bindings<'_> {
+    fn transacting(&self) -> impl Iterator<Item = &FunctionSignature> {
+        self.functions
+            .iter()
+            .map(|(_, function)| function)
+            .chain(&self.receive)
+            .chain(&self.fallback)
+    }
+    /// The names the bindings import from the abstraction.
+    fn abstraction_imports(&self) -> Vec<&'static str> {
+        let mut named = vec![];
+        if self.transacting().any(|function| !function.reads()) {
+            named.push("ContractTransaction");
+        }
+        if self.deployment.is_some() {
+            named.push("AbstractDeployer");
+        }
+        for overrides in ["Overrides", "PayableOverrides"] {
+            if self
+                .transacting()
+                .chain(self.deployment.iter().map(|(_, constructor)| constructor))
+                .any(|function| function.overrides() == Some(overrides))
+            {
+                named.push(overrides);
+            }
+        }
+        named
+    }
+    /// The names the bindings declare, besides their structs and class.
+    fn declared(&self) -> Vec<String> {
+        let mut names: Vec<_> = self
+            .events
+            .iter()
+            .map(|event| event.interface.clone())
+            .chain(self.errors.iter().map(|error| error.class.clone()))
+            .collect();
+        if !self.errors.is_empty() {
+            names.push(format!("{}Error", self.name));
+            names.push(format!("decode{}Error", self.name));
+        }
+        if self.deployment.is_some() {
+            names.push(format!("{}Factory", self.name));
+        }
+        names
+    }
+}
+
+/// Claims a name for a declaration of the given shape, which is the name
+/// itself unless claimed before for another shape.
+fn claim(
+    shapes: &mut Vec<(String, Vec<(String, ts::Type)>)>,
+    contract: &str,
+    name: &str,
+    shape: Vec<(String, ts::Type)>,
+) -> String {
+    match shapes.iter().find(|(claimed, _)| claimed == name) {
+        None => {
+            shapes.push((name.to_owned(), shape));
+            name.to_owned()
+        }
+        Some((_, claimed)) if *claimed == shape => name.to_owned(),
+        Some(_) => claim(shapes, contract, &format!("{}{}", contract, name), shape),
+    }
+}
+
+/// The type of the block range bounds when querying past events.
+fn block_tag() -> ts::Type {
+    ts::Type::Union(vec![ts::Type::Number, ts::Type::String])
+}
+
+/// The types accepted by the numeric fields of the transaction overrides.
+fn overrides_numeric() -> ts::Type {
+    ts::Type::Union(vec![ts::Type::BigInt, ts::Type::Number, ts::Type::String])
+}
+
+/// Turns a signature into a name usable in code, like `E_uint256_array`
+/// for `E(uint256[])`.
+fn signature_identifier(signature: &str) -> String {
+    let mut identifier = String::new();
+    for c in signature.chars() {
+        match c {
+            '[' => identifier.push_str("_array"),
+            ']' => {}
+            c if c.is_alphanumeric() => identifier.push(c),
+            _ if identifier.ends_with('_') => {}
+            _ => identifier.push('_'),
+        }
+    }
+    identifier.trim_end_matches('_').to_owned()
+}
+
+/// Whether a name can be used as is for a class or a variable.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
 /// The code emitter grabs the ABI data and generates code based on
 /// the information provided by it.
-pub struct CodeEmitter;
+#[derive(Debug, Clone, Default)]
+pub struct CodeEmitter {
+    /// How integers too wide for a `number` are typed.
+    pub numeric: NumericMapping,
+    pub target: Target,
+    /// How the methods wrapping functions are named.
+    pub methods: Case,
+}
 impl CodeEmitter {
-    pub fn emit_contract_abstraction(self) -> String {
-        ts::Script::new()
+    pub fn emit_contract_abstraction(&self) -> String {
+        let builder = self.emit_target_imports(ts::Script::new());
+        self.emit_abstraction(builder, ts::Export::Default)
+            .collect()
+    }
+    /// Emits the imports of the implementations of the abstraction.
+    fn emit_target_imports(&self, builder: ts::Script) -> ts::Script {
+        match self.target {
+            Target::Generic => builder,
+            Target::EthersV5 => {
+                let mut named = vec!["Contract", "ContractFactory", "ContractInterface", "Signer"];
+                if !matches!(self.numeric, NumericMapping::Class { .. }) {
+                    named.insert(0, "BigNumber");
+                }
+                builder.import().named(&named).from("ethers").import_end()
+            }
+            Target::EthersV6 => builder
+                .import()
+                .named(&["Contract", "ContractFactory", "InterfaceAbi", "Signer"])
+                .from("ethers")
+                .import_end(),
+        }
+    }
+    /// Emits the abstraction the bindings are built on, exporting
+    /// `AbstractContract` as told.
+    fn emit_abstraction(&self, builder: ts::Script, export: ts::Export) -> ts::Script {
+        let builder = builder
             .class(
-                "AbstractContract",
-                ts::Export::Default,
+                "ContractTransaction",
+                ts::Export::Named,
                 ts::ClassType::Interface,
             )
-            .method("call", false, ts::Visibility::NotSpecified)
-            .param("target", ts::Type::String)
-            .rest_param("args", ts::Type::Array(Box::new(ts::Type::Any)))
+            .property("hash", ts::Type::String)
+            .method("wait", false, ts::Visibility::NotSpecified)
+            .optional_param("confirmations", ts::Type::Number)
             .method_end_abstract(ts::Type::Promise(Box::new(ts::Type::Unknown)))
             .class_end()
-            .collect()
+            .class("Overrides", ts::Export::Named, ts::ClassType::Interface)
+            .optional_property("gasLimit", overrides_numeric())
+            .optional_property("gasPrice", overrides_numeric())
+            .optional_property("maxFeePerGas", overrides_numeric())
+            .optional_property("maxPriorityFeePerGas", overrides_numeric())
+            .optional_property("nonce", ts::Type::Number)
+            .class_end()
+            .class(
+                "PayableOverrides extends Overrides",
+                ts::Export::Named,
+                ts::ClassType::Interface,
+            )
+            .optional_property("value", overrides_numeric())
+            .class_end()
+            .class("DecodedError", ts::Export::Named, ts::ClassType::Interface)
+            .property("name", ts::Type::String)
+            .property("args", ts::Type::Array(Box::new(ts::Type::Any)))
+            .class_end()
+            .class(
+                "AbstractDeployer",
+                ts::Export::Named,
+                ts::ClassType::Interface,
+            )
+            .method("deploy", false, ts::Visibility::NotSpecified)
+            .param("name", ts::Type::String)
+            .param("bytecode", ts::Type::String)
+            .param("args", ts::Type::Array(Box::new(ts::Type::Any)))
+            .optional_param("overrides", ts::Type::Class("PayableOverrides".into()))
+            .method_end_abstract(ts::Type::Promise(Box::new(ts::Type::Class(
+                "AbstractContract".into(),
+            ))))
+            .class_end()
+            .class("AbstractContract", export, ts::ClassType::Interface)
+            .method("read<T>", false, ts::Visibility::NotSpecified)
+            .param("target", ts::Type::String)
+            .rest_param("args", ts::Type::Array(Box::new(ts::Type::Any)))
+            .method_end_abstract(ts::Type::Promise(Box::new(ts::Type::Class("T".into()))))
+            .method("send", false, ts::Visibility::NotSpecified)
+            .param("target", ts::Type::String)
+            .param("args", ts::Type::Array(Box::new(ts::Type::Any)))
+            .optional_param("overrides", ts::Type::Class("PayableOverrides".into()))
+            .method_end_abstract(ts::Type::Promise(Box::new(ts::Type::Class(
+                "ContractTransaction".into(),
+            ))))
+            .method("sendRaw", false, ts::Visibility::NotSpecified)
+            .param("data", ts::Type::String)
+            .optional_param("overrides", ts::Type::Class("PayableOverrides".into()))
+            .method_end_abstract(ts::Type::Promise(Box::new(ts::Type::Class(
+                "ContractTransaction".into(),
+            ))))
+            .method("on<T>", false, ts::Visibility::NotSpecified)
+            .param("event", ts::Type::String)
+            .param(
+                "listener",
+                ts::Type::Function(
+                    vec![("event".into(), ts::Type::Class("T".into()))],
+                    Box::new(ts::Type::Void),
+                ),
+            )
+            .method_end_abstract(ts::Type::Function(vec![], Box::new(ts::Type::Void)))
+            .method("query<T>", false, ts::Visibility::NotSpecified)
+            .param("event", ts::Type::String)
+            .param("filter", ts::Type::Array(Box::new(ts::Type::Any)))
+            .optional_param("fromBlock", block_tag())
+            .optional_param("toBlock", block_tag())
+            .method_end_abstract(ts::Type::Promise(Box::new(ts::Type::Array(Box::new(
+                ts::Type::Class("T".into()),
+            )))))
+            .method("decodeError", false, ts::Visibility::NotSpecified)
+            .param("data", ts::Type::String)
+            .method_end_abstract(ts::Type::Union(vec![
+                ts::Type::Class("DecodedError".into()),
+                ts::Type::Undefined,
+            ]))
+            .class_end();
+        match self.target {
+            Target::Generic => builder,
+            Target::EthersV5 => self.emit_ethers_v5(builder),
+            Target::EthersV6 => Self::emit_ethers_v6(builder),
+        }
     }
-    /// Emits the whole class code
-    pub fn emit(self, contract: &Contract) -> Result<String, ()> {
-        let builder = ts::Script::new()
-            .import()
-            .by_default("AbstractContract")
-            .from("./AbstractContract")
-            .import_end();
+    /// The names the abstraction exports, besides `AbstractContract`.
+    pub fn abstraction_exports(&self) -> Vec<&'static str> {
+        let mut names = vec![
+            "ContractTransaction",
+            "Overrides",
+            "PayableOverrides",
+            "DecodedError",
+            "AbstractDeployer",
+        ];
+        if self.target != Target::Generic {
+            names.extend(["EthersContract", "EthersDeployer"]);
+        }
+        names
+    }
+    /// Emits the implementations of the abstraction over ethers v5, which
+    /// hands out `BigNumber`s that are turned into `bigint`s unless those
+    /// are the numeric class.
+    fn emit_ethers_v5(&self, builder: ts::Script) -> ts::Script {
+        let bigint = !matches!(self.numeric, NumericMapping::Class { .. });
+        let convert = |value: &str| {
+            if bigint {
+                format!("fromEthers({})", value)
+            } else {
+                value.to_owned()
+            }
+        };
+        let builder = if bigint {
+            let builder = builder
+                .function("fromEthers", ts::Export::Private)
+                .param("value", ts::Type::Any)
+                .body_returning(ts::Type::Any)
+                .block("if (BigNumber.isBigNumber(value))");
+            let builder = statement(builder, "return value.toBigInt()")
+                .block_end()
+                .block("if (Array.isArray(value))");
+            // Results hold the named outputs as keys besides the indices.
+            let builder = statement(builder, "const result: any = value.map(fromEthers)")
+                .block("for (const key of Object.keys(value).filter((key) => isNaN(Number(key))))");
+            let builder =
+                statement(builder, "result[key] = fromEthers((value as any)[key])").block_end();
+            let builder = statement(builder, "return result").block_end();
+            statement(builder, "return value").block_end()
+        } else {
+            builder
+        };
         let builder = builder
             .class(
-                contract.name.clone(),
-                ts::Export::Default,
+                "EthersContract implements AbstractContract",
+                ts::Export::Named,
                 ts::ClassType::Normal,
             )
             .constructor()
             .field(
                 "contract",
-                ts::Type::Class("AbstractContract".into()),
+                ts::Type::Class("Contract".into()),
+                true,
+                ts::Visibility::Public,
+            )
+            .constructor_end()
+            .method("read<T>", true, ts::Visibility::Public)
+            .param("target", ts::Type::String)
+            .rest_param("args", ts::Type::Array(Box::new(ts::Type::Any)))
+            .body_returning(ts::Type::Promise(Box::new(ts::Type::Class("T".into()))));
+        let builder = statement(
+            builder,
+            &format!(
+                "return {}",
+                convert("await this.contract.callStatic[target](...args)")
+            ),
+        )
+        .method_end()
+        .method("send", true, ts::Visibility::Public)
+        .param("target", ts::Type::String)
+        .param("args", ts::Type::Array(Box::new(ts::Type::Any)))
+        .optional_param("overrides", ts::Type::Class("PayableOverrides".into()))
+        .body_returning(ts::Type::Promise(Box::new(ts::Type::Class(
+            "ContractTransaction".into(),
+        ))));
+        let builder = statement(
+            builder,
+            "return await this.contract.functions[target](...args, overrides ?? {})",
+        )
+        .method_end()
+        .method("sendRaw", true, ts::Visibility::Public)
+        .param("data", ts::Type::String)
+        .optional_param("overrides", ts::Type::Class("PayableOverrides".into()))
+        .body_returning(ts::Type::Promise(Box::new(ts::Type::Class(
+            "ContractTransaction".into(),
+        ))));
+        let builder = statement(
+            builder,
+            "return await this.contract.signer.sendTransaction({ ...overrides, to: this.contract.address, data })",
+        )
+        .method_end()
+        .method("on<T>", false, ts::Visibility::Public)
+        .param("event", ts::Type::String)
+        .param(
+            "listener",
+            ts::Type::Function(
+                vec![("event".into(), ts::Type::Class("T".into()))],
+                Box::new(ts::Type::Void),
+            ),
+        )
+        .body_returning(ts::Type::Function(vec![], Box::new(ts::Type::Void)));
+        // Listeners get the arguments of the event, and the event itself last.
+        let builder = statements(
+            builder,
+            &[
+                &format!(
+                    "const handler = (...args: Array<any>) => listener({})",
+                    convert("args[args.length - 1].args")
+                ),
+                "this.contract.on(event, handler)",
+                "return () => void this.contract.off(event, handler)",
+            ],
+        )
+        .method_end()
+        .method("query<T>", true, ts::Visibility::Public)
+        .param("event", ts::Type::String)
+        .param("filter", ts::Type::Array(Box::new(ts::Type::Any)))
+        .optional_param("fromBlock", block_tag())
+        .optional_param("toBlock", block_tag())
+        .body_returning(ts::Type::Promise(Box::new(ts::Type::Array(Box::new(
+            ts::Type::Class("T".into()),
+        )))));
+        let builder = statements(
+            builder,
+            &[
+                "const events = await this.contract.queryFilter(this.contract.filters[event](...filter), fromBlock, toBlock)",
+                &format!("return events.map((event) => {})", convert("event.args")),
+            ],
+        )
+        .method_end()
+        .method("decodeError", false, ts::Visibility::Public)
+        .param("data", ts::Type::String)
+        .body_returning(ts::Type::Union(vec![
+            ts::Type::Class("DecodedError".into()),
+            ts::Type::Undefined,
+        ]))
+        // Unknown errors make the interface throw.
+        .block("try");
+        let builder = statements(
+            builder,
+            &[
+                "const error = this.contract.interface.parseError(data)",
+                &format!(
+                    "return {{ name: error.name, args: {} }}",
+                    convert("[...error.args]")
+                ),
+            ],
+        )
+        .block_next("catch");
+        let builder = statement(builder, "return undefined")
+            .block_end()
+            .method_end()
+            .class_end()
+            .class(
+                "EthersDeployer implements AbstractDeployer",
+                ts::Export::Named,
+                ts::ClassType::Normal,
+            )
+            .constructor()
+            .field(
+                "signer",
+                ts::Type::Class("Signer".into()),
+                true,
+                ts::Visibility::Private,
+            )
+            .field(
+                "abis",
+                ts::Type::Record(
+                    Box::new(ts::Type::String),
+                    Box::new(ts::Type::Class("ContractInterface".into())),
+                ),
                 true,
                 ts::Visibility::Private,
             )
             .constructor_end();
-        let builder = contract.abi.iter().fold(builder, |builder, entry| {
-            if let AbiEntry::Function {
+        let builder = Self::emit_deploy(builder);
+        statements(
+            builder,
+            &[
+                "const factory = new ContractFactory(this.abis[name], bytecode, this.signer)",
+                "const contract = await factory.deploy(...args, overrides ?? {})",
+                "return new EthersContract(await contract.deployed())",
+            ],
+        )
+        .method_end()
+        .class_end()
+    }
+    /// Emits the implementations of the abstraction over ethers v6, which
+    /// already hands out `bigint`s.
+    fn emit_ethers_v6(builder: ts::Script) -> ts::Script {
+        let builder = builder
+            .class(
+                "EthersContract implements AbstractContract",
+                ts::Export::Named,
+                ts::ClassType::Normal,
+            )
+            .constructor()
+            .field(
+                "contract",
+                ts::Type::Class("Contract".into()),
+                true,
+                ts::Visibility::Public,
+            )
+            .constructor_end()
+            .method("read<T>", true, ts::Visibility::Public)
+            .param("target", ts::Type::String)
+            .rest_param("args", ts::Type::Array(Box::new(ts::Type::Any)))
+            .body_returning(ts::Type::Promise(Box::new(ts::Type::Class("T".into()))));
+        let builder = statement(
+            builder,
+            "return await this.contract.getFunction(target).staticCall(...args)",
+        )
+        .method_end()
+        .method("send", true, ts::Visibility::Public)
+        .param("target", ts::Type::String)
+        .param("args", ts::Type::Array(Box::new(ts::Type::Any)))
+        .optional_param("overrides", ts::Type::Class("PayableOverrides".into()))
+        .body_returning(ts::Type::Promise(Box::new(ts::Type::Class(
+            "ContractTransaction".into(),
+        ))));
+        let builder = statement(
+            builder,
+            "return await this.contract.getFunction(target).send(...args, overrides ?? {})",
+        )
+        .method_end()
+        .method("sendRaw", true, ts::Visibility::Public)
+        .param("data", ts::Type::String)
+        .optional_param("overrides", ts::Type::Class("PayableOverrides".into()))
+        .body_returning(ts::Type::Promise(Box::new(ts::Type::Class(
+            "ContractTransaction".into(),
+        ))));
+        let builder = statements(
+            builder,
+            &[
+                "const signer = this.contract.runner as Signer",
+                "return await signer.sendTransaction({ ...overrides, to: await this.contract.getAddress(), data })",
+            ],
+        )
+        .method_end()
+        .method("on<T>", false, ts::Visibility::Public)
+        .param("event", ts::Type::String)
+        .param(
+            "listener",
+            ts::Type::Function(
+                vec![("event".into(), ts::Type::Class("T".into()))],
+                Box::new(ts::Type::Void),
+            ),
+        )
+        .body_returning(ts::Type::Function(vec![], Box::new(ts::Type::Void)));
+        // Listeners get the arguments of the event, and its payload last.
+        let builder = statements(
+            builder,
+            &[
+                "const handler = (...args: Array<any>) => listener(args[args.length - 1].args.toObject())",
+                "void this.contract.on(event, handler)",
+                "return () => void this.contract.off(event, handler)",
+            ],
+        )
+        .method_end()
+        .method("query<T>", true, ts::Visibility::Public)
+        .param("event", ts::Type::String)
+        .param("filter", ts::Type::Array(Box::new(ts::Type::Any)))
+        .optional_param("fromBlock", block_tag())
+        .optional_param("toBlock", block_tag())
+        .body_returning(ts::Type::Promise(Box::new(ts::Type::Array(Box::new(
+            ts::Type::Class("T".into()),
+        )))));
+        let builder = statements(
+            builder,
+            &[
+                "const events = await this.contract.queryFilter(this.contract.filters[event](...filter), fromBlock, toBlock)",
+                "return events.map((event) => (\"args\" in event ? event.args.toObject() : {}) as T)",
+            ],
+        )
+        .method_end()
+        .method("decodeError", false, ts::Visibility::Public)
+        .param("data", ts::Type::String)
+        .body_returning(ts::Type::Union(vec![
+            ts::Type::Class("DecodedError".into()),
+            ts::Type::Undefined,
+        ]));
+        let builder = statements(
+            builder,
+            &[
+                "const error = this.contract.interface.parseError(data)",
+                "return error === null ? undefined : { name: error.name, args: [...error.args] }",
+            ],
+        )
+        .method_end()
+        .class_end()
+        .class(
+            "EthersDeployer implements AbstractDeployer",
+            ts::Export::Named,
+            ts::ClassType::Normal,
+        )
+        .constructor()
+        .field(
+            "signer",
+            ts::Type::Class("Signer".into()),
+            true,
+            ts::Visibility::Private,
+        )
+        .field(
+            "abis",
+            ts::Type::Record(
+                Box::new(ts::Type::String),
+                Box::new(ts::Type::Class("InterfaceAbi".into())),
+            ),
+            true,
+            ts::Visibility::Private,
+        )
+        .constructor_end();
+        let builder = Self::emit_deploy(builder);
+        statements(
+            builder,
+            &[
+                "const factory = new ContractFactory(this.abis[name], bytecode, this.signer)",
+                "const contract = await factory.deploy(...args, overrides ?? {})",
+                "await contract.waitForDeployment()",
+                "return new EthersContract(new Contract(await contract.getAddress(), this.abis[name], this.signer))",
+            ],
+        )
+        .method_end()
+        .class_end()
+    }
+    /// Emits the signature of `AbstractDeployer.deploy`, up to its body.
+    fn emit_deploy(builder: ts::Class) -> ts::Script {
+        builder
+            .method("deploy", true, ts::Visibility::Public)
+            .param("name", ts::Type::String)
+            .param("bytecode", ts::Type::String)
+            .param("args", ts::Type::Array(Box::new(ts::Type::Any)))
+            .optional_param("overrides", ts::Type::Class("PayableOverrides".into()))
+            .body_returning(ts::Type::Promise(Box::new(ts::Type::Class(
+                "AbstractContract".into(),
+            ))))
+    }
+    /// Emits a class for each custom error, the union of all of them, and
+    /// the function that decodes revert data into that union.
+    /// Classes already in `declared` are left out.
+    fn emit_errors(
+        builder: ts::Script,
+        contract: &str,
+        errors: &[ErrorSignature],
+        declared: &mut Vec<String>,
+    ) -> ts::Script {
+        let builder = errors.iter().fold(builder, |builder, error| {
+            if declared.contains(&error.class) {
+                return builder;
+            }
+            declared.push(error.class.clone());
+            let ErrorSignature {
                 name,
-                mutability: _,
-                constant: _,
-                inputs,
-                outputs: _,
-            } = entry
-            {
-                let builder = builder.method(name.clone(), true, ts::Visibility::Public);
-                let mut i = -1;
-                let builder = inputs
+                class,
+                params,
+            } = error;
+            let builder = builder
+                .class(
+                    format!("{} extends Error", class),
+                    ts::Export::Named,
+                    ts::ClassType::Normal,
+                )
+                .readonly("name", format!("\"{}\" as const", name))
+                .constructor();
+            params
+                .iter()
+                .fold(builder, |builder, (param, kind)| {
+                    builder.field(param, kind.clone(), true, ts::Visibility::Public)
+                })
+                .body()
+                .expression()
+                .field("super")
+                .call()
+                .param()
+                .string(name)
+                .param_end()
+                .call_end()
+                .expression_end()
+                .method_end()
+                .class_end()
+        });
+        let union = format!("{}Error", contract);
+        let builder = builder
+            .type_alias(
+                &union,
+                ts::Export::Named,
+                ts::Type::Union(
+                    errors
+                        .iter()
+                        .map(|error| ts::Type::Class(error.class.clone()))
+                        .collect(),
+                ),
+            )
+            .function(format!("decode{}", union), ts::Export::Named)
+            .param("contract", ts::Type::Class("AbstractContract".into()))
+            .param("data", ts::Type::String)
+            .body_returning(ts::Type::Union(vec![
+                ts::Type::Class(union.clone()),
+                ts::Type::Undefined,
+            ]))
+            .declare_const("error")
+            .field("contract")
+            .dot()
+            .field("decodeError")
+            .call()
+            .param()
+            .field("data")
+            .param_end()
+            .call_end()
+            .expression_end()
+            .block("if (error === undefined)")
+            .expression()
+            .do_return()
+            .field("undefined")
+            .expression_end()
+            .block_end()
+            .block("switch (error.name)");
+        errors
+            .iter()
+            .fold(builder, |builder, error| {
+                let builder = builder
+                    .case(format!("\"{}\"", error.name))
+                    .expression()
+                    .do_return()
+                    .construct(&error.class)
+                    .call();
+                (0..error.params.len())
+                    .fold(builder, |builder, i| {
+                        builder
+                            .param()
+                            .field("error")
+                            .dot()
+                            .field("args")
+                            .index(i)
+                            .param_end()
+                    })
+                    .call_end()
+                    .expression_end()
+                    .case_end()
+            })
+            .block_end()
+            .expression()
+            .do_return()
+            .field("undefined")
+            .expression_end()
+            .block_end()
+    }
+    /// Emits the wrapper method of a function, which calls `target` on the
+    /// contract.
+    fn emit_function(
+        builder: ts::Class,
+        method: &str,
+        target: &str,
+        function: &FunctionSignature,
+    ) -> ts::Class {
+        let builder = builder.method(method, true, ts::Visibility::Public);
+        let builder = function
+            .params
+            .iter()
+            .fold(builder, |builder, (param, kind)| {
+                builder.param(param, kind.clone())
+            });
+        let builder = match function.overrides() {
+            Some(overrides) => {
+                builder.optional_param("overrides", ts::Type::Class(overrides.into()))
+            }
+            None => builder,
+        };
+        let builder = builder
+            .body_returning(ts::Type::Promise(Box::new(function.resolves_to())))
+            .expression()
+            .do_return()
+            .do_await()
+            .field("this")
+            .dot()
+            .field("contract")
+            .dot();
+        let builder = if function.reads() {
+            let builder = builder
+                .field("read")
+                .type_argument(function.returns.clone())
+                .call()
+                .param()
+                .string(target)
+                .param_end();
+            function.params.iter().fold(builder, |builder, (param, _)| {
+                builder.param().field(param).param_end()
+            })
+        } else {
+            let builder = builder
+                .field("send")
+                .call()
+                .param()
+                .string(target)
+                .param_end()
+                .param()
+                .array();
+            function
+                .params
+                .iter()
+                .fold(builder, |builder, (param, _)| {
+                    builder.item().field(param).item_end()
+                })
+                .array_end()
+                .param_end()
+                .param()
+                .field("overrides")
+                .param_end()
+        };
+        builder.call_end().expression_end().method_end()
+    }
+    /// Emits overloaded functions as an alias method per signature, like
+    /// `"transfer(address,uint256)"`, plus overload signatures under the
+    /// plain name that dispatch on the number of arguments. Overloads that
+    /// take the same number of arguments can't be told apart at runtime, so
+    /// they are only reachable through their aliases, and there is no plain
+    /// method when none can be told apart.
+    fn emit_overloads(
+        builder: ts::Class,
+        name: &str,
+        overloads: &[&(String, FunctionSignature)],
+    ) -> ts::Class {
+        let overloads_taking = |arity: usize| {
+            overloads
+                .iter()
+                .filter(|(_, function)| function.params.len() == arity)
+                .count()
+        };
+        let distinct: Vec<_> = overloads
+            .iter()
+            .filter(|(_, function)| overloads_taking(function.params.len()) == 1)
+            .collect();
+        let builder = distinct.iter().fold(builder, |builder, (_, function)| {
+            let arity = function.params.len();
+            let builder = function.params.iter().fold(
+                builder.method(name, false, ts::Visibility::Public),
+                |builder, (param, kind)| builder.param(param, kind.clone()),
+            );
+            let builder = match function.overrides() {
+                Some(overrides) if overloads_taking(arity + 1) == 0 => {
+                    builder.optional_param("overrides", ts::Type::Class(overrides.into()))
+                }
+                _ => builder,
+            };
+            builder.method_end_abstract(ts::Type::Promise(Box::new(function.resolves_to())))
+        });
+        // The overload each number of arguments goes to, including the
+        // trailing overrides when no overload takes that many. The others
+        // are ambiguous, which untyped callers are told about.
+        let mut dispatch: Vec<(usize, Option<&String>)> = vec![];
+        for (signature, function) in overloads.iter().map(|overload| (&overload.0, &overload.1)) {
+            let arity = function.params.len();
+            if overloads_taking(arity) > 1 {
+                dispatch.push((arity, None));
+                continue;
+            }
+            dispatch.push((arity, Some(signature)));
+            if function.overrides().is_some() && overloads_taking(arity + 1) == 0 {
+                dispatch.push((arity + 1, Some(signature)));
+            }
+        }
+        dispatch.sort_by_key(|(arity, _)| *arity);
+        dispatch.dedup();
+        let ambiguous = |arity: usize| {
+            overloads
+                .iter()
+                .filter(|(_, function)| function.params.len() == arity)
+                .map(|(signature, _)| format!("this[\\\"{}\\\"]", signature))
+                .collect::<Vec<_>>()
+                .join(" or ")
+        };
+        let builder = if distinct.is_empty() {
+            builder
+        } else {
+            let builder = builder
+                .method(name, true, ts::Visibility::Public)
+                .rest_param("args", ts::Type::Array(Box::new(ts::Type::Any)))
+                .body_returning(ts::Type::Promise(Box::new(ts::Type::Any)))
+                .block("switch (args.length)");
+            dispatch
+                .iter()
+                .fold(builder, |builder, (arity, signature)| {
+                    let builder = builder.case(arity).expression();
+                    let builder = match signature {
+                        Some(signature) => {
+                            let builder = builder
+                                .do_return()
+                                .do_await()
+                                .field("this")
+                                .key(signature)
+                                .call();
+                            (0..*arity)
+                                .fold(builder, |builder, i| {
+                                    builder.param().field("args").index(i).param_end()
+                                })
+                                .call_end()
+                        }
+                        None => builder
+                            .do_throw()
+                            .construct("Error")
+                            .call()
+                            .param()
+                            .string(format!(
+                                "Ambiguous call to {}, use {} instead",
+                                name,
+                                ambiguous(*arity)
+                            ))
+                            .param_end()
+                            .call_end(),
+                    };
+                    builder.expression_end().case_end()
+                })
+                .block_end()
+                .expression()
+                .do_throw()
+                .construct("Error")
+                .call()
+                .param()
+                .string(format!("No overload of {} takes that many arguments", name))
+                .param_end()
+                .call_end()
+                .expression_end()
+                .method_end()
+        };
+        overloads
+            .iter()
+            .fold(builder, |builder, (signature, function)| {
+                Self::emit_function(builder, &format!("\"{}\"", signature), signature, function)
+            })
+    }
+    /// Translates a contract, registering its structs in the given types.
+    fn translate<'a>(contract: &'a Contract, types: &mut Types) -> Result<Bindings<'a>, Error> {
+        if !is_identifier(&contract.name) {
+            return Err(Error::Emit {
+                contract: contract.name.clone(),
+                message: "the contract name isn't a valid class name".into(),
+            });
+        }
+        let functions: Vec<_> = contract
+            .abi
+            .iter()
+            .filter_map(|entry| match entry {
+                AbiEntry::Function {
+                    name,
+                    mutability,
+                    inputs,
+                    outputs,
+                    ..
+                } => Some((
+                    signature(name, inputs),
+                    FunctionSignature {
+                        name: name.clone(),
+                        params: avoid(types.params(inputs, Direction::Input), &["overrides"]),
+                        returns: types.returns(outputs),
+                        mutability: mutability.clone(),
+                    },
+                )),
+                _ => None,
+            })
+            .collect();
+        // Anonymous events can't be told apart by name, so they are left out.
+        let events: Vec<_> = contract
+            .abi
+            .iter()
+            .filter_map(|entry| match entry {
+                AbiEntry::Event {
+                    anonymous: false,
+                    name,
+                    inputs,
+                } => Some((name, inputs)),
+                _ => None,
+            })
+            .collect();
+        // Overloaded events are told apart by their signatures.
+        let events = events
+            .iter()
+            .map(|(name, inputs)| {
+                let overloaded = events.iter().filter(|(other, _)| other == name).count() > 1;
+                let target = if overloaded {
+                    event_signature(name, inputs)
+                } else {
+                    name.to_string()
+                };
+                EventSignature {
+                    name: name.to_string(),
+                    interface: format!("{}Event", signature_identifier(&target)),
+                    target,
+                    fields: types.event_fields(inputs),
+                }
+            })
+            .collect();
+        let errors: Vec<_> = contract
+            .abi
+            .iter()
+            .filter_map(|entry| match entry {
+                AbiEntry::Error { name, inputs } => Some(ErrorSignature {
+                    name: name.clone(),
+                    class: name.clone(),
+                    // Error classes have these already.
+                    params: avoid(
+                        types.params(inputs, Direction::Output),
+                        &["name", "message", "stack"],
+                    ),
+                }),
+                _ => None,
+            })
+            .collect();
+        // Abstract contracts and interfaces can't be deployed.
+        let deployment = contract
+            .bytecode
+            .as_ref()
+            .filter(|bytecode| !bytecode.trim_start_matches("0x").is_empty())
+            .map(|bytecode| {
+                let constructor = contract
+                    .abi
                     .iter()
-                    .fold(builder, |builder, io| {
-                        builder.param(
-                            if io.name.is_empty() {
-                                i += 1;
-                                format!("_param{}", i)
-                            } else {
-                                io.name.clone()
-                            },
-                            translate_type(io.io_type.clone()),
-                        )
+                    .find_map(|entry| match entry {
+                        AbiEntry::Constructor { inputs, mutability } => Some(FunctionSignature {
+                            name: "constructor".into(),
+                            params: avoid(types.ctor_params(inputs), &["overrides"]),
+                            returns: ts::Type::Void,
+                            mutability: mutability.clone(),
+                        }),
+                        _ => None,
                     })
-                    .body()
+                    .unwrap_or(FunctionSignature {
+                        name: "constructor".into(),
+                        params: vec![],
+                        returns: ts::Type::Void,
+                        mutability: StateMutability::NonPayable,
+                    });
+                (bytecode.as_str(), constructor)
+            });
+        let libraries = contract
+            .link_references
+            .iter()
+            .flat_map(|(source, libraries)| {
+                libraries.iter().map(move |(library, references)| {
+                    (format!("{}:{}", source, library), &references[..])
+                })
+            })
+            .collect();
+        // Ether sent through `receive` is given apart from the overrides.
+        let receive = contract
+            .abi
+            .iter()
+            .find(|entry| matches!(entry, AbiEntry::Receive { .. }))
+            .map(|_| FunctionSignature {
+                name: "sendEther".into(),
+                params: vec![("value".into(), overrides_numeric())],
+                returns: ts::Type::Void,
+                mutability: StateMutability::NonPayable,
+            });
+        let fallback = contract.abi.iter().find_map(|entry| match entry {
+            AbiEntry::Fallback { mutability } => Some(FunctionSignature {
+                name: "fallback".into(),
+                params: vec![("data".into(), ts::Type::String)],
+                returns: ts::Type::Void,
+                mutability: mutability.clone(),
+            }),
+            _ => None,
+        });
+        Ok(Bindings {
+            name: &contract.name,
+            functions,
+            events,
+            errors,
+            deployment,
+            libraries,
+            receive,
+            fallback,
+        })
+    }
+    /// Emits an interface for each struct.
+    fn emit_structs(builder: ts::Script, structs: &[Struct]) -> ts::Script {
+        structs.iter().fold(builder, |builder, item| {
+            item.fields
+                .iter()
+                .fold(
+                    builder.class(&item.name, ts::Export::Named, ts::ClassType::Interface),
+                    |builder, (name, kind)| builder.property(name, kind.clone()),
+                )
+                .class_end()
+        })
+    }
+    /// Emits the types that go along a contract: the arguments of its
+    /// events and its custom errors, unless in `declared`.
+    fn emit_declarations(
+        builder: ts::Script,
+        bindings: &Bindings,
+        declared: &mut Vec<String>,
+    ) -> ts::Script {
+        let builder = bindings.events.iter().fold(builder, |builder, event| {
+            if declared.contains(&event.interface) {
+                return builder;
+            }
+            declared.push(event.interface.clone());
+            event
+                .fields
+                .iter()
+                .fold(
+                    builder.class(
+                        &event.interface,
+                        ts::Export::Named,
+                        ts::ClassType::Interface,
+                    ),
+                    |builder, field| builder.property(&field.name, field.kind.clone()),
+                )
+                .class_end()
+        });
+        if bindings.errors.is_empty() {
+            builder
+        } else {
+            Self::emit_errors(builder, bindings.name, &bindings.errors, declared)
+        }
+    }
+    /// Emits the bindings of a contract, importing the abstraction from the
+    /// given module, like `./AbstractContract`.
+    pub fn emit(&self, contract: &Contract, abstraction: &str) -> Result<String, Error> {
+        let mut types = Types::new(self.numeric.clone(), self.target);
+        let bindings = Self::translate(contract, &mut types)?;
+        let import = ts::Script::new().import().by_default("AbstractContract");
+        let named = bindings.abstraction_imports();
+        let import = if named.is_empty() {
+            import
+        } else {
+            import.named(&named)
+        };
+        let builder = import.from(abstraction).import_end();
+        let builder = match &types.numeric_import {
+            Some((name, module)) => builder.import().named(&[name]).from(module).import_end(),
+            None => builder,
+        };
+        let builder = Self::emit_structs(builder, &types.structs);
+        let builder = Self::emit_declarations(builder, &bindings, &mut vec![]);
+        Ok(self
+            .emit_class(builder, &bindings, ts::Export::Default)
+            .collect())
+    }
+    /// The names the module of a contract exports, besides its class.
+    pub fn exports(&self, contract: &Contract) -> Result<Vec<String>, Error> {
+        let mut types = Types::new(self.numeric.clone(), self.target);
+        let bindings = Self::translate(contract, &mut types)?;
+        Ok(types
+            .structs
+            .iter()
+            .map(|item| item.name.clone())
+            .chain(bindings.declared())
+            .collect())
+    }
+    /// Emits a barrel re-exporting the abstraction and the modules of the
+    /// contracts, given their paths, classes and other exports. Names
+    /// exported by an earlier module are left to it.
+    pub fn emit_index(&self, modules: &[(String, &str, &[String])]) -> String {
+        let mut named = vec!["default as AbstractContract"];
+        named.extend(self.abstraction_exports());
+        let builder = ts::Script::new()
+            .export()
+            .named(&named)
+            .from("./AbstractContract")
+            .import_end();
+        let mut exported: Vec<String> = vec!["AbstractContract".into()];
+        exported.extend(named[1..].iter().map(|name| name.to_string()));
+        modules
+            .iter()
+            .fold(builder, |builder, (path, class, exports)| {
+                let mut named = vec![];
+                if !exported.contains(&class.to_string()) {
+                    exported.push(class.to_string());
+                    named.push(format!("default as {}", class));
+                }
+                for name in exports.iter() {
+                    if !exported.contains(name) {
+                        exported.push(name.clone());
+                        named.push(name.clone());
+                    }
+                }
+                if named.is_empty() {
+                    builder
+                } else {
+                    builder.export().named(&named).from(path).import_end()
+                }
+            })
+            .collect()
+    }
+    /// Emits the bindings of many contracts as a single module, along with
+    /// the abstraction, everything exported by name. Structs are shared by
+    /// the contracts declaring them, and so are events and errors unless
+    /// they differ, in which case the later ones are prefixed by their
+    /// contract name.
+    pub fn emit_bundle(&self, contracts: &[&Contract]) -> Result<String, Error> {
+        let mut types = Types::new(self.numeric.clone(), self.target);
+        let mut bundled = vec![];
+        for contract in contracts {
+            let bindings = Self::translate(contract, &mut types)?;
+            if bundled
+                .iter()
+                .any(|known: &Bindings| known.name == bindings.name)
+            {
+                return Err(Error::Emit {
+                    contract: contract.name.clone(),
+                    message: "another contract of the bundle has the same name".into(),
+                });
+            }
+            bundled.push(bindings);
+        }
+        let mut shapes = vec![];
+        for bindings in bundled.iter_mut() {
+            for event in bindings.events.iter_mut() {
+                let fields = event
+                    .fields
+                    .iter()
+                    .map(|field| (field.name.clone(), field.kind.clone()))
+                    .collect();
+                event.interface = claim(&mut shapes, bindings.name, &event.interface, fields);
+            }
+            for error in bindings.errors.iter_mut() {
+                error.class = claim(
+                    &mut shapes,
+                    bindings.name,
+                    &error.class,
+                    error.params.clone(),
+                );
+            }
+        }
+        let builder = self.emit_target_imports(ts::Script::new());
+        let builder = match &types.numeric_import {
+            Some((name, module)) => builder.import().named(&[name]).from(module).import_end(),
+            None => builder,
+        };
+        let builder = self.emit_abstraction(builder, ts::Export::Named);
+        let builder = Self::emit_structs(builder, &types.structs);
+        let mut declared = vec![];
+        Ok(bundled
+            .iter()
+            .fold(builder, |builder, bindings| {
+                let builder = Self::emit_declarations(builder, bindings, &mut declared);
+                self.emit_class(builder, bindings, ts::Export::Named)
+            })
+            .collect())
+    }
+    /// Emits the class wrapping a contract, and the factory deploying it.
+    fn emit_class(
+        &self,
+        builder: ts::Script,
+        bindings: &Bindings,
+        export: ts::Export,
+    ) -> ts::Script {
+        let Bindings {
+            name: contract,
+            functions,
+            events,
+            deployment,
+            libraries,
+            receive,
+            fallback,
+            ..
+        } = bindings;
+        let builder = builder
+            .class(contract, export, ts::ClassType::Normal)
+            .constructor()
+            .field(
+                "contract",
+                ts::Type::Class("AbstractContract".into()),
+                true,
+                ts::Visibility::Private,
+            )
+            .constructor_end();
+        let mut names: Vec<&str> = vec![];
+        for (_, function) in functions.iter() {
+            if !names.contains(&function.name.as_str()) {
+                names.push(&function.name);
+            }
+        }
+        let builder = names.iter().fold(builder, |builder, name| {
+            let overloads: Vec<_> = functions
+                .iter()
+                .filter(|(_, function)| function.name == *name)
+                .collect();
+            // Recasing can't make a method clash with another function.
+            let method = match self.methods.apply(name) {
+                method if method != *name && names.contains(&method.as_str()) => name.to_string(),
+                method => method,
+            };
+            match overloads[..] {
+                [(_, function)] => Self::emit_function(builder, &method, name, function),
+                _ => Self::emit_overloads(builder, &method, &overloads),
+            }
+        });
+        let builder = match receive {
+            Some(receive) => receive
+                .params
+                .iter()
+                .fold(
+                    builder.method(&receive.name, true, ts::Visibility::Public),
+                    |builder, (param, kind)| builder.param(param, kind.clone()),
+                )
+                .optional_param("overrides", ts::Type::Class("Overrides".into()))
+                .body_returning(ts::Type::Promise(Box::new(ts::Type::Class(
+                    "ContractTransaction".into(),
+                ))))
+                .expression()
+                .do_return()
+                .do_await()
+                .field("this")
+                .dot()
+                .field("contract")
+                .dot()
+                .field("sendRaw")
+                .call()
+                .param()
+                .string("0x")
+                .param_end()
+                .param()
+                .object()
+                .spread("overrides")
+                .shorthand("value")
+                .object_end()
+                .param_end()
+                .call_end()
+                .expression_end()
+                .method_end(),
+            None => builder,
+        };
+        let builder = match fallback {
+            Some(fallback) => {
+                let overrides = fallback.overrides().unwrap_or("Overrides");
+                fallback
+                    .params
+                    .iter()
+                    .fold(
+                        builder.method(&fallback.name, true, ts::Visibility::Public),
+                        |builder, (param, kind)| builder.param(param, kind.clone()),
+                    )
+                    .optional_param("overrides", ts::Type::Class(overrides.into()))
+                    .body_returning(ts::Type::Promise(Box::new(ts::Type::Class(
+                        "ContractTransaction".into(),
+                    ))))
                     .expression()
                     .do_return()
                     .do_await()
@@ -89,33 +1863,212 @@ impl CodeEmitter {
                     .dot()
                     .field("contract")
                     .dot()
-                    .field("call")
+                    .field("sendRaw")
                     .call()
                     .param()
-                    .string(name.clone())
-                    .param_end();
-                i = -1;
-                inputs
-                    .iter()
-                    .fold(builder, |builder, io| {
-                        builder
-                            .param()
-                            .field(if io.name.is_empty() {
-                                i += 1;
-                                format!("_param{}", i)
-                            } else {
-                                io.name.clone()
-                            })
-                            .param_end()
-                    })
+                    .field("data")
+                    .param_end()
+                    .param()
+                    .field("overrides")
+                    .param_end()
                     .call_end()
                     .expression_end()
                     .method_end()
-            } else {
-                builder
             }
+            None => builder,
+        };
+        let builder = events.iter().fold(builder, |builder, event| {
+            let listener = ts::Type::Function(
+                vec![("event".into(), ts::Type::Class(event.interface.clone()))],
+                Box::new(ts::Type::Void),
+            );
+            let builder = builder
+                .method(event.method("on"), false, ts::Visibility::Public)
+                .param("listener", listener)
+                .body_returning(ts::Type::Function(vec![], Box::new(ts::Type::Void)))
+                .expression()
+                .do_return()
+                .field("this")
+                .dot()
+                .field("contract")
+                .dot()
+                .field("on")
+                .type_argument(ts::Type::Class(event.interface.clone()))
+                .call()
+                .param()
+                .string(event.target.clone())
+                .param_end()
+                .param()
+                .field("listener")
+                .param_end()
+                .call_end()
+                .expression_end()
+                .method_end();
+            let builder = builder
+                .method(event.method("query"), true, ts::Visibility::Public)
+                .param("filter", event.filter())
+                .optional_param("fromBlock", block_tag())
+                .optional_param("toBlock", block_tag())
+                .body_returning(ts::Type::Promise(Box::new(ts::Type::Array(Box::new(
+                    ts::Type::Class(event.interface.clone()),
+                )))))
+                .expression()
+                .do_return()
+                .do_await()
+                .field("this")
+                .dot()
+                .field("contract")
+                .dot()
+                .field("query")
+                .type_argument(ts::Type::Class(event.interface.clone()))
+                .call()
+                .param()
+                .string(event.target.clone())
+                .param_end()
+                .param()
+                .array();
+            // Queries match topics by position, with `null` as a wildcard.
+            event
+                .fields
+                .iter()
+                .filter(|field| field.indexed)
+                .fold(builder, |builder, field| {
+                    builder
+                        .item()
+                        .field("filter")
+                        .dot()
+                        .field(&field.name)
+                        .coalesce()
+                        .null()
+                        .item_end()
+                })
+                .array_end()
+                .param_end()
+                .param()
+                .field("fromBlock")
+                .param_end()
+                .param()
+                .field("toBlock")
+                .param_end()
+                .call_end()
+                .expression_end()
+                .method_end()
         });
         let builder = builder.class_end();
-        Ok(builder.collect())
+        let builder = match deployment {
+            Some((bytecode, constructor)) => {
+                let factory = format!("{}Factory", contract);
+                let builder = builder
+                    .class(&factory, ts::Export::Named, ts::ClassType::Normal)
+                    .static_readonly("bytecode", ts::Type::String, format!("\"{}\"", bytecode))
+                    .constructor()
+                    .field(
+                        "deployer",
+                        ts::Type::Class("AbstractDeployer".into()),
+                        true,
+                        ts::Visibility::Private,
+                    );
+                // Linked bytecode needs the addresses of its libraries.
+                let builder = if libraries.is_empty() {
+                    builder
+                } else {
+                    builder.field(
+                        "libraries",
+                        ts::Type::Interface(
+                            libraries
+                                .iter()
+                                .map(|(library, _)| (library.clone(), ts::Type::String))
+                                .collect(),
+                        ),
+                        true,
+                        ts::Visibility::Private,
+                    )
+                };
+                let builder =
+                    builder
+                        .constructor_end()
+                        .method("deploy", true, ts::Visibility::Public);
+                let builder = constructor
+                    .params
+                    .iter()
+                    .fold(builder, |builder, (param, kind)| {
+                        builder.param(param, kind.clone())
+                    });
+                let builder = match constructor.overrides() {
+                    Some(overrides) => {
+                        builder.optional_param("overrides", ts::Type::Class(overrides.into()))
+                    }
+                    None => builder,
+                };
+                let builder = builder
+                    .body_returning(ts::Type::Promise(Box::new(ts::Type::Class(
+                        contract.to_string(),
+                    ))))
+                    .expression()
+                    .do_return()
+                    .construct(contract)
+                    .call()
+                    .param()
+                    .do_await()
+                    .field("this")
+                    .dot()
+                    .field("deployer")
+                    .dot()
+                    .field("deploy")
+                    .call()
+                    .param()
+                    .string(contract)
+                    .param_end()
+                    .param();
+                let builder = if libraries.is_empty() {
+                    builder.field(&factory).dot().field("bytecode")
+                } else {
+                    builder.field("this").dot().field("link").call().call_end()
+                };
+                let builder = builder.param_end().param().array();
+                let builder = constructor
+                    .params
+                    .iter()
+                    .fold(builder, |builder, (param, _)| {
+                        builder.item().field(param).item_end()
+                    })
+                    .array_end()
+                    .param_end()
+                    .param()
+                    .field("overrides")
+                    .param_end()
+                    .call_end()
+                    .param_end()
+                    .call_end()
+                    .expression_end()
+                    .method_end();
+                if libraries.is_empty() {
+                    builder.class_end()
+                } else {
+                    // Addresses take as many characters as the placeholders they
+                    // replace, so the offsets hold as they are written.
+                    let builder = builder
+                        .method("link", false, ts::Visibility::Private)
+                        .body_returning(ts::Type::String);
+                    let mut code = vec![format!("let bytecode = {}.bytecode", factory)];
+                    for (library, references) in libraries {
+                        for LinkReference { start, length } in references.iter() {
+                            let start = 2 + start * 2;
+                            code.push(format!(
+                                "bytecode = bytecode.slice(0, {}) + this.libraries[\"{}\"].slice(2) + bytecode.slice({})",
+                                start,
+                                library,
+                                start + length * 2
+                            ));
+                        }
+                    }
+                    code.push("return bytecode".into());
+                    let code: Vec<_> = code.iter().map(String::as_str).collect();
+                    statements(builder, &code).method_end().class_end()
+                }
+            }
+            None => builder,
+        };
+        builder
     }
 }
@@ -0,0 +1,135 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::error::{Error, Location};
+
+/// The names of the configuration files, by preference.
+pub const FILE_NAMES: [&str; 2] = ["spider-jockey.toml", ".spiderjockeyrc"];
+
+/// Names picked by glob patterns, like `I*` or `src/Token.sol:*`.
+#[derive(Debug, Default, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Filter {
+    /// The only names allowed, unless empty.
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl Filter {
+    fn matches(patterns: &[String], names: &[&str]) -> bool {
+        patterns.iter().any(|pattern| {
+            glob::Pattern::new(pattern)
+                .is_ok_and(|pattern| names.iter().any(|name| pattern.matches(name)))
+        })
+    }
+
+    /// Whether anything known by the given names is allowed.
+    pub fn allows(&self, names: &[&str]) -> bool {
+        (self.include.is_empty() || Self::matches(&self.include, names))
+            && !Self::matches(&self.exclude, names)
+    }
+
+    fn check(&self) -> Result<(), String> {
+        for pattern in self.include.iter().chain(&self.exclude) {
+            glob::Pattern::new(pattern)
+                .map_err(|err| format!("bad pattern `{}`: {}", pattern, err))?;
+        }
+        Ok(())
+    }
+}
+
+/// How the outputs are named.
+#[derive(Debug, Default, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Naming {
+    /// The case of the files written for contracts.
+    pub files: Option<String>,
+    /// The case of the methods wrapping functions.
+    pub methods: Option<String>,
+}
+
+/// The settings of a single contract, which replace the project ones.
+#[derive(Debug, Default, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Override {
+    pub numeric: Option<String>,
+    pub naming: Naming,
+    pub functions: Option<Filter>,
+}
+
+/// The options read from a configuration file, which the command line
+/// overrides.
+#[derive(Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    pub inputs: Vec<String>,
+    pub out_dir: Option<PathBuf>,
+    pub root: Option<PathBuf>,
+    pub target: Option<String>,
+    pub numeric: Option<String>,
+    pub bundle: Option<PathBuf>,
+    pub index: bool,
+    pub keep_going: bool,
+    pub naming: Naming,
+    pub contracts: Filter,
+    pub functions: Filter,
+    /// Keyed by contract name, or by `source:Name`.
+    pub overrides: BTreeMap<String, Override>,
+}
+
+impl Config {
+    /// Reads a configuration, whose paths are relative to the directory
+    /// holding it.
+    pub fn read(path: &Path, contents: &str) -> Result<Config, Error> {
+        let mut config: Config = toml::from_str(contents).map_err(|err| {
+            let location = err.span().map(|span| {
+                let before = &contents[..span.start];
+                Location {
+                    line: before.matches('\n').count() + 1,
+                    column: before.len() - before.rfind('\n').map_or(0, |at| at + 1) + 1,
+                    path: String::new(),
+                }
+            });
+            Error::Parse {
+                file: Some(path.into()),
+                location,
+                message: err.message().trim_end().to_owned(),
+            }
+        })?;
+        let filters = [&config.contracts, &config.functions].into_iter().chain(
+            config
+                .overrides
+                .values()
+                .flat_map(|overrides| &overrides.functions),
+        );
+        for filter in filters {
+            filter
+                .check()
+                .map_err(|message| Error::parse(message).in_file(path))?;
+        }
+        let base = path.parent().unwrap_or(Path::new(""));
+        config.inputs = config
+            .inputs
+            .iter()
+            .map(|input| base.join(input).to_string_lossy().into_owned())
+            .collect();
+        config.out_dir = config.out_dir.map(|dir| base.join(dir));
+        config.root = config.root.map(|dir| base.join(dir));
+        Ok(config)
+    }
+
+    /// Finds the configuration of a directory, looking up its ancestors,
+    /// returning its path relative to the directory.
+    pub fn discover(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors().enumerate().find_map(|(depth, ancestor)| {
+            FILE_NAMES
+                .iter()
+                .find(|name| ancestor.join(name).is_file())
+                .map(|name| Path::new(&"../".repeat(depth)).join(name))
+        })
+    }
+}
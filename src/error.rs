@@ -0,0 +1,125 @@
+use std::{fmt, io, path::PathBuf};
+
+/// Where in an artifact something went wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    /// The path of the offending value, like `abi[12].inputs[0].internalType`.
+    pub path: String,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// A file that couldn't be read or written.
+    Io { file: PathBuf, source: io::Error },
+    /// An artifact that couldn't be read, with the file it came from once
+    /// known.
+    Parse {
+        file: Option<PathBuf>,
+        location: Option<Location>,
+        message: String,
+    },
+    /// A contract that can't be turned into code.
+    Emit { contract: String, message: String },
+    /// Arguments that make no sense.
+    Usage(String),
+}
+
+impl Error {
+    /// An error found in an artifact, but not at any place of it.
+    pub fn parse<S>(message: S) -> Self
+    where
+        S: ToString,
+    {
+        Error::Parse {
+            file: None,
+            location: None,
+            message: message.to_string(),
+        }
+    }
+
+    /// Tells which file a parse error comes from.
+    pub fn in_file<P>(self, path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        match self {
+            Error::Parse {
+                file: None,
+                location,
+                message,
+            } => Error::Parse {
+                file: Some(path.into()),
+                location,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+impl From<serde_path_to_error::Error<serde_json::Error>> for Error {
+    fn from(err: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        let path = err.path().to_string();
+        let inner = err.into_inner();
+        let (line, column) = (inner.line(), inner.column());
+        // serde_json appends the position to the message, which is told apart
+        // here instead.
+        let message = inner.to_string();
+        let message = message
+            .strip_suffix(&format!(" at line {} column {}", line, column))
+            .unwrap_or(&message)
+            .to_owned();
+        Error::Parse {
+            file: None,
+            location: Some(Location {
+                line,
+                column,
+                path: if path == "." { String::new() } else { path },
+            }),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { file, source } => write!(f, "{}: {}", file.display(), source),
+            Error::Parse {
+                file,
+                location,
+                message,
+            } => {
+                if let Some(file) = file {
+                    write!(f, "{}:", file.display())?;
+                }
+                if let Some(location) = location {
+                    write!(f, "{}:{}:", location.line, location.column)?;
+                }
+                if file.is_some() || location.is_some() {
+                    f.write_str(" ")?;
+                }
+                f.write_str(message)?;
+                match location {
+                    Some(location) if !location.path.is_empty() => {
+                        write!(f, " (at {})", location.path)
+                    }
+                    _ => Ok(()),
+                }
+            }
+            Error::Emit { contract, message } => write!(f, "{}: {}", contract, message),
+            Error::Usage(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use crate::parser::{AbiEntry, CtorInput, DataType, EventInput, FuncIO, StateMutability};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Open,
+    Close,
+    Comma,
+}
+
+/// Splits a signature into words and punctuation, keeping array suffixes
+/// like `[2][]` as words of their own or attached to the type before them.
+fn tokenize(signature: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut word = String::new();
+    for c in signature.chars() {
+        if c.is_alphanumeric() || matches!(c, '_' | '$' | '[' | ']' | '.') {
+            word.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            tokens.push(Token::Word(std::mem::take(&mut word)));
+        }
+        match c {
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            ',' => tokens.push(Token::Comma),
+            ';' => {}
+            c if c.is_whitespace() => {}
+            c => return Err(format!("unexpected `{}`", c)),
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(Token::Word(word));
+    }
+    Ok(tokens)
+}
+
+/// A parameter before knowing which kind of entry it belongs to.
+struct Param {
+    name: String,
+    io_type: DataType,
+    components: Vec<FuncIO>,
+    indexed: bool,
+}
+
+impl From<Param> for FuncIO {
+    fn from(param: Param) -> Self {
+        FuncIO {
+            name: param.name,
+            internal_type: param.io_type.clone(),
+            io_type: param.io_type,
+            components: param.components,
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("expected {:?}, found {:?}", expected, token)),
+            None => Err(format!("expected {:?}, found the end", expected)),
+        }
+    }
+    fn word(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Word(word)) => Ok(word),
+            Some(token) => Err(format!("expected a word, found {:?}", token)),
+            None => Err("expected a word, found the end".into()),
+        }
+    }
+    /// Consumes the next word if it is the given keyword.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        if self.peek() == Some(&Token::Word(keyword.into())) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+    /// Parses a parenthesized list of parameters.
+    fn params(&mut self) -> Result<Vec<Param>, String> {
+        self.expect(Token::Open)?;
+        let mut params = vec![];
+        if self.peek() == Some(&Token::Close) {
+            self.position += 1;
+            return Ok(params);
+        }
+        loop {
+            params.push(self.param()?);
+            match self.next() {
+                Some(Token::Comma) => {}
+                Some(Token::Close) => return Ok(params),
+                Some(token) => return Err(format!("expected `,` or `)`, found {:?}", token)),
+                None => return Err("unclosed parameter list".into()),
+            }
+        }
+    }
+    /// Parses a type, either elementary or a tuple like `tuple(a, b)[]` or
+    /// `(a, b)`, followed by its modifiers and name.
+    fn param(&mut self) -> Result<Param, String> {
+        let (io_type, components) = if self.peek() == Some(&Token::Open) || self.keyword("tuple") {
+            let components = self.params()?.into_iter().map(FuncIO::from).collect();
+            let suffix = match self.peek() {
+                Some(Token::Word(word)) if word.starts_with('[') => self.word()?,
+                _ => String::new(),
+            };
+            (format!("tuple{}", suffix).parse()?, components)
+        } else {
+            let mut io_type = self.word()?;
+            if io_type == "address" && self.keyword("payable") {
+                io_type.push_str(" payable");
+            }
+            (io_type.parse()?, vec![])
+        };
+        let mut param = Param {
+            name: String::new(),
+            io_type,
+            components,
+            indexed: false,
+        };
+        while let Some(Token::Word(word)) = self.peek() {
+            match word.as_str() {
+                "indexed" => param.indexed = true,
+                "memory" | "calldata" | "storage" => {}
+                name => param.name = name.into(),
+            }
+            self.position += 1;
+        }
+        Ok(param)
+    }
+    /// Parses the modifiers after the parameters, up to `returns`.
+    fn mutability(&mut self) -> StateMutability {
+        let mut mutability = StateMutability::NonPayable;
+        while let Some(Token::Word(word)) = self.peek() {
+            match word.as_str() {
+                "view" | "constant" => mutability = StateMutability::View,
+                "pure" => mutability = StateMutability::Pure,
+                "payable" => mutability = StateMutability::Payable,
+                "nonpayable" | "external" | "public" | "internal" | "private" => {}
+                _ => break,
+            }
+            self.position += 1;
+        }
+        mutability
+    }
+    fn entry(&mut self) -> Result<AbiEntry, String> {
+        let entry = match self.word()?.as_str() {
+            "function" => {
+                let name = self.word()?;
+                let inputs = self.params()?.into_iter().map(FuncIO::from).collect();
+                let mutability = self.mutability();
+                let outputs = if self.keyword("returns") {
+                    self.params()?.into_iter().map(FuncIO::from).collect()
+                } else {
+                    vec![]
+                };
+                AbiEntry::Function {
+                    name,
+                    constant: false,
+                    mutability,
+                    inputs,
+                    outputs,
+                }
+            }
+            "event" => {
+                let name = self.word()?;
+                let inputs = self
+                    .params()?
+                    .into_iter()
+                    .map(|param| EventInput {
+                        indexed: param.indexed,
+                        internal_type: param.io_type.clone(),
+                        name: param.name,
+                        input_type: param.io_type,
+                        components: param.components,
+                    })
+                    .collect();
+                let anonymous = self.keyword("anonymous");
+                AbiEntry::Event {
+                    anonymous,
+                    name,
+                    inputs,
+                }
+            }
+            "error" => AbiEntry::Error {
+                name: self.word()?,
+                inputs: self.params()?.into_iter().map(FuncIO::from).collect(),
+            },
+            "constructor" => AbiEntry::Constructor {
+                inputs: self
+                    .params()?
+                    .into_iter()
+                    .map(|param| CtorInput {
+                        name: param.name,
+                        internal_type: param.io_type.clone(),
+                        input_type: param.io_type,
+                        components: param.components,
+                    })
+                    .collect(),
+                mutability: self.mutability(),
+            },
+            "fallback" => {
+                self.params()?;
+                AbiEntry::Fallback {
+                    mutability: self.mutability(),
+                }
+            }
+            "receive" => {
+                self.params()?;
+                AbiEntry::Receive {
+                    mutability: self.mutability(),
+                }
+            }
+            other => return Err(format!("unknown entry kind `{}`", other)),
+        };
+        match self.peek() {
+            None => Ok(entry),
+            Some(token) => Err(format!("unexpected {:?} after the entry", token)),
+        }
+    }
+}
+
+/// Parses a human-readable ABI, like
+/// `function balanceOf(address owner) view returns (uint256)`.
+pub fn parse_abi<S>(signatures: &[S]) -> Result<Vec<AbiEntry>, String>
+where
+    S: AsRef<str>,
+{
+    signatures
+        .iter()
+        .map(|signature| {
+            let signature = signature.as_ref();
+            tokenize(signature)
+                .and_then(|tokens| {
+                    Parser {
+                        tokens,
+                        position: 0,
+                    }
+                    .entry()
+                })
+                .map_err(|err| format!("{} in `{}`", err, signature))
+        })
+        .collect()
+}
+
+/// Writes a type, spelling out the components of tuples.
+fn format_type(io_type: &DataType, components: &[FuncIO]) -> String {
+    match io_type {
+        DataType::Array(inner, Some(size)) => {
+            format!("{}[{}]", format_type(inner, components), size)
+        }
+        DataType::Array(inner, None) => format!("{}[]", format_type(inner, components)),
+        DataType::Tuple => format!("tuple({})", format_params(components)),
+        other => other.to_string(),
+    }
+}
+
+fn format_param(io_type: &DataType, components: &[FuncIO], indexed: bool, name: &str) -> String {
+    let mut param = format_type(io_type, components);
+    if indexed {
+        param.push_str(" indexed");
+    }
+    if !name.is_empty() {
+        param.push(' ');
+        param.push_str(name);
+    }
+    param
+}
+
+fn format_params(params: &[FuncIO]) -> String {
+    params
+        .iter()
+        .map(|io| format_param(&io.io_type, &io.components, false, &io.name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The modifier written for a mutability, which nonpayable lacks.
+fn format_mutability(mutability: &StateMutability) -> &'static str {
+    match mutability {
+        StateMutability::NonPayable => "",
+        StateMutability::Payable => " payable",
+        StateMutability::View => " view",
+        StateMutability::Pure => " pure",
+    }
+}
+
+/// Writes an ABI back as human-readable signatures.
+pub fn format_abi(abi: &[AbiEntry]) -> Vec<String> {
+    abi.iter()
+        .map(|entry| match entry {
+            AbiEntry::Function {
+                name,
+                mutability,
+                inputs,
+                outputs,
+                ..
+            } => {
+                let mut signature = format!(
+                    "function {}({}){}",
+                    name,
+                    format_params(inputs),
+                    format_mutability(mutability)
+                );
+                if !outputs.is_empty() {
+                    signature.push_str(&format!(" returns ({})", format_params(outputs)));
+                }
+                signature
+            }
+            AbiEntry::Event {
+                anonymous,
+                name,
+                inputs,
+            } => format!(
+                "event {}({}){}",
+                name,
+                inputs
+                    .iter()
+                    .map(|io| format_param(&io.input_type, &io.components, io.indexed, &io.name))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                if *anonymous { " anonymous" } else { "" }
+            ),
+            AbiEntry::Error { name, inputs } => {
+                format!("error {}({})", name, format_params(inputs))
+            }
+            AbiEntry::Constructor { inputs, mutability } => format!(
+                "constructor({}){}",
+                inputs
+                    .iter()
+                    .map(|io| format_param(&io.input_type, &io.components, false, &io.name))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                format_mutability(mutability)
+            ),
+            AbiEntry::Fallback { mutability } => {
+                format!("fallback() external{}", format_mutability(mutability))
+            }
+            AbiEntry::Receive { .. } => "receive() external payable".into(),
+        })
+        .collect()
+}
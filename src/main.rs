@@ -1,33 +1,474 @@
 use std::{
     env,
-    fs::File,
+    ffi::OsStr,
+    fs::{self, File},
     io::{Read, Write},
+    path::{Path, PathBuf},
+    process, thread,
+    time::{Duration, SystemTime},
 };
 
-use crate::{code_emitter::CodeEmitter, parser::Contract};
+use crate::{
+    check::HEADER,
+    cli::{Command, Options, Verbosity},
+    error::Error,
+    human_readable::format_abi,
+    parser::{read_artifact, Contract},
+};
 
 #[cfg(test)]
 mod test;
 
+mod check;
+mod cli;
 mod code_emitter;
+mod config;
+mod error;
+mod human_readable;
 mod parser;
 pub mod ts;
 
+fn read(path: &str) -> Result<String, Error> {
+    let mut contents = String::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_string(&mut contents))
+        .map_err(|source| Error::Io {
+            file: path.into(),
+            source,
+        })?;
+    Ok(contents)
+}
+
+/// Writes a file, making the directories it goes in.
+fn write(path: &Path, contents: &[u8]) -> Result<(), Error> {
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| File::create(path))
+        .and_then(|mut file| file.write_all(contents))
+        .map_err(|source| Error::Io {
+            file: path.into(),
+            source,
+        })
+}
+
+/// Where the outputs go: to disk, or compared against it by `--check`.
+enum Sink {
+    Disk,
+    Check {
+        /// Every output generated, stale or not.
+        expected: Vec<PathBuf>,
+        /// The diffs of the outputs differing from the disk.
+        diffs: Vec<String>,
+    },
+}
+
+impl Sink {
+    fn put(&mut self, path: &Path, contents: &str) -> Result<(), Error> {
+        match self {
+            Sink::Disk => write(path, contents.as_bytes()),
+            Sink::Check { expected, diffs } => {
+                expected.push(path.to_owned());
+                let before = check::read_existing(path)?;
+                if before.as_deref() != Some(contents) {
+                    diffs.push(check::diff(path, before.as_deref(), Some(contents)));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// How many outputs differ from the disk so far.
+    fn stale(&self) -> usize {
+        match self {
+            Sink::Disk => 0,
+            Sink::Check { diffs, .. } => diffs.len(),
+        }
+    }
+}
+
+/// Marks TypeScript as generated, so that `--check` can tell it from the
+/// files written by hand.
+fn generated(code: &str) -> String {
+    format!("{}{}", HEADER, code)
+}
+
+/// Reads the contracts of an input, leaving out the contracts and functions
+/// not asked for.
+fn contracts_of(options: &Options, path: &str, name: Option<&str>) -> Result<Vec<Contract>, Error> {
+    let input = read(path)?;
+    // `ERC20.abi.json` holds the `ERC20` contract.
+    let stem = Path::new(path)
+        .file_name()
+        .and_then(OsStr::to_str)
+        .and_then(|file_name| file_name.split('.').next());
+    let contracts = read_artifact(&input, name.or(stem)).map_err(|err| err.in_file(path))?;
+    Ok(options.select(contracts))
+}
+
+/// A file written for a contract, along with what the index re-exports
+/// from it.
+struct Module {
+    file: PathBuf,
+    class: String,
+    exports: Vec<String>,
+}
+
+/// Generates the code of the contracts of an input, returning the files
+/// written.
+fn compile(
+    options: &Options,
+    path: &str,
+    name: Option<&str>,
+    sink: &mut Sink,
+) -> Result<Vec<Module>, Error> {
+    let contracts = contracts_of(options, path, name)?;
+    let out_dir = options.out_dir_of(path)?;
+    let abstraction = options.abstraction_import_of(path)?;
+    // Contracts of different sources may share a name, and so a file.
+    let file_names: Vec<_> = contracts
+        .iter()
+        .map(|contract| options.file_name_of(contract))
+        .collect();
+    for (i, contract) in contracts.iter().enumerate() {
+        if let Some(other) = (0..i).find(|&other| file_names[other] == file_names[i]) {
+            return Err(Error::Emit {
+                contract: contract.qualified_name(),
+                message: format!(
+                    "it would overwrite the file of `{}`, pick one of them with `--contract`",
+                    contracts[other].qualified_name()
+                ),
+            });
+        }
+    }
+    let mut written = vec![];
+    for contract in contracts {
+        if options.human_readable {
+            let signatures = format_abi(&contract.abi);
+            let mut output = serde_json::to_string_pretty(&signatures).unwrap_or_default();
+            output.push('\n');
+            let file = out_dir.join(format!("{}.abi.json", options.file_name_of(&contract)));
+            sink.put(&file, &output)?;
+            written.push(Module {
+                file,
+                class: contract.name,
+                exports: vec![],
+            });
+            continue;
+        }
+        let emitter = options.emitter_of(&contract);
+        let output = emitter.emit(&contract, &abstraction)?;
+        let file = out_dir.join(format!("{}.ts", options.file_name_of(&contract)));
+        sink.put(&file, &generated(&output))?;
+        written.push(Module {
+            file,
+            exports: emitter.exports(&contract)?,
+            class: contract.name,
+        });
+    }
+    Ok(written)
+}
+
+/// Writes the index re-exporting every module, returning its path.
+fn write_index(options: &Options, modules: &[&Module], sink: &mut Sink) -> Result<PathBuf, Error> {
+    let modules: Vec<_> = modules
+        .iter()
+        .map(|module| {
+            let path = module
+                .file
+                .strip_prefix(&options.out_dir)
+                .unwrap_or(&module.file)
+                .with_extension("");
+            let path = path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            (
+                format!("./{}", path),
+                module.class.as_str(),
+                &module.exports[..],
+            )
+        })
+        .collect();
+    let file = options.out_dir.join("index.ts");
+    sink.put(&file, &generated(&options.emitter.emit_index(&modules)))?;
+    Ok(file)
+}
+
+/// An input, along with what was last generated from it.
+struct Input {
+    path: String,
+    name: Option<String>,
+    modified: Option<SystemTime>,
+    /// The contracts read, when bundling them.
+    contracts: Vec<Contract>,
+    /// The files written, otherwise.
+    modules: Vec<Module>,
+}
+
+impl Input {
+    fn new(path: String, name: Option<String>) -> Self {
+        Input {
+            path,
+            name,
+            modified: None,
+            contracts: vec![],
+            modules: vec![],
+        }
+    }
+
+    fn modified(&self) -> Option<SystemTime> {
+        fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+}
+
+/// Reads or compiles an input, printing how it went. A failed input keeps
+/// what was generated from it before.
+fn process(options: &Options, input: &mut Input, sink: &mut Sink) -> Result<(), Error> {
+    let quiet = options.verbosity == Verbosity::Quiet;
+    if !quiet {
+        let verb = if options.check {
+            "Checking"
+        } else {
+            "Compiling"
+        };
+        print!("{} {}...", verb, input.path);
+    }
+    let stale = sink.stale();
+    input.modified = input.modified();
+    let name = input.name.as_deref();
+    let result = if options.bundle.is_some() {
+        contracts_of(options, &input.path, name).map(|contracts| {
+            let found: Vec<_> = contracts
+                .iter()
+                .map(|contract| contract.name.clone())
+                .collect();
+            input.contracts = contracts;
+            (!found.is_empty()).then(|| format!("found {}", found.join(", ")))
+        })
+    } else {
+        compile(options, &input.path, name, sink).map(|written| {
+            let files: Vec<_> = written
+                .iter()
+                .map(|module| module.file.display().to_string())
+                .collect();
+            input.modules = written;
+            (!files.is_empty()).then(|| format!("see {}", files.join(", ")))
+        })
+    };
+    match &result {
+        Ok(_) if !quiet && sink.stale() > stale => println!(" STALE"),
+        Ok(_) if !quiet && options.check => println!(" OK!"),
+        Ok(None) if !quiet => println!(" OK! no contract picked"),
+        Ok(Some(report)) if !quiet => println!(" OK! {}", report),
+        Err(_) if !quiet => println!(" FAILED"),
+        _ => {}
+    }
+    result.map(|_| ())
+}
+
+/// Writes the files shared by the inputs: the bundle, or the abstraction
+/// and the index.
+fn finish(
+    options: &Options,
+    inputs: &[Input],
+    abstraction: bool,
+    sink: &mut Sink,
+) -> Result<(), Error> {
+    let quiet = options.verbosity == Verbosity::Quiet;
+    let verbose = options.verbosity == Verbosity::Verbose;
+    if let Some(bundle) = &options.bundle {
+        let contracts: Vec<_> = inputs.iter().flat_map(|input| &input.contracts).collect();
+        let file = options.out_dir.join(bundle);
+        sink.put(&file, &generated(&options.emitter.emit_bundle(&contracts)?))?;
+        if !quiet {
+            println!(
+                "Bundled {} contracts into {}",
+                contracts.len(),
+                file.display()
+            );
+        }
+        return Ok(());
+    }
+    if options.human_readable {
+        return Ok(());
+    }
+    if abstraction {
+        let file = options.out_dir.join("AbstractContract.ts");
+        sink.put(
+            &file,
+            &generated(&options.emitter.emit_contract_abstraction()),
+        )?;
+        if verbose {
+            println!("Wrote {}", file.display());
+        }
+    }
+    if options.index {
+        let modules: Vec<_> = inputs.iter().flat_map(|input| &input.modules).collect();
+        let file = write_index(options, &modules, sink)?;
+        if verbose {
+            println!("Wrote {}", file.display());
+        }
+    }
+    Ok(())
+}
+
+/// How often the inputs are looked at when watching them.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Regenerates the outputs of the inputs that change, including the ones
+/// that start or stop matching the glob patterns, forever. Malformed inputs,
+/// which compilers write halfway through, are reported and retried once
+/// they change again.
+fn watch(options: &Options, mut inputs: Vec<Input>) -> ! {
+    if options.verbosity != Verbosity::Quiet {
+        println!("Watching {} inputs, press Ctrl-C to stop", inputs.len());
+    }
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        poll(options, &mut inputs);
+    }
+}
+
+/// Looks at the inputs once, regenerating the outputs of the ones that
+/// changed, and returns whether any did.
+fn poll(options: &Options, inputs: &mut Vec<Input>) -> bool {
+    let expanded = match cli::expand_inputs(&options.patterns, false) {
+        Ok(expanded) => expanded,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return false;
+        }
+    };
+    let expanded: Vec<_> = expanded
+        .into_iter()
+        .filter(|(path, _)| !path.ends_with(".dbg.json"))
+        .collect();
+    let before = inputs.len();
+    inputs.retain(|input| {
+        let kept = expanded.iter().any(|(path, _)| *path == input.path);
+        if !kept && options.verbosity != Verbosity::Quiet {
+            println!("Dropping {}, which is gone", input.path);
+        }
+        kept
+    });
+    let mut changed = inputs.len() != before;
+    for (path, name) in expanded {
+        if !inputs.iter().any(|input| input.path == path) {
+            inputs.push(Input::new(path, name));
+        }
+    }
+    // Inputs gone missing, which compilers delete before writing them again,
+    // are reported once until they are back.
+    for input in inputs.iter_mut() {
+        if input.modified() == input.modified {
+            continue;
+        }
+        changed = true;
+        if let Err(err) = process(options, input, &mut Sink::Disk) {
+            eprintln!("error: {}", err);
+        }
+    }
+    if changed {
+        if let Err(err) = finish(options, inputs, false, &mut Sink::Disk) {
+            eprintln!("error: {}", err);
+        }
+    }
+    changed
+}
+
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    for arg in args.iter().skip(1) {
-        print!("Compiling {}...", arg);
-        let mut input = File::open(arg).unwrap();
-        let mut input_str = String::new();
-        input.read_to_string(&mut input_str).unwrap();
-        let contract = Contract::from_str(&input_str).unwrap();
-        let output = CodeEmitter.emit(&contract).unwrap();
-        let mut out = File::create(format!("{}.ts", contract.name)).unwrap();
-        out.write_all(output.as_bytes()).unwrap();
-        println!(" OK! see {}.ts", contract.name);
-    }
-    let mut out = File::create("AbstractContract.ts").unwrap();
-    out.write_all(CodeEmitter.emit_contract_abstraction().as_bytes())
-        .unwrap();
-    println!("All done!");
+    let options = match cli::parse_args(env::args().skip(1)) {
+        Ok(Command::Run(options)) => *options,
+        Ok(Command::Help) => {
+            print!("{}", cli::USAGE);
+            return;
+        }
+        Ok(Command::Version) => {
+            println!("spider-jockey {}", env!("CARGO_PKG_VERSION"));
+            return;
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(2);
+        }
+    };
+    let quiet = options.verbosity == Verbosity::Quiet;
+    // Watching carries on through failures, which get fixed as it goes.
+    let keep_going = options.keep_going || options.watch;
+    let mut sink = if options.check {
+        Sink::Check {
+            expected: vec![],
+            diffs: vec![],
+        }
+    } else {
+        Sink::Disk
+    };
+    let mut inputs = vec![];
+    let mut failed = 0;
+    for (path, name) in options.inputs.iter() {
+        // Hardhat writes one next to every artifact, so globs pick them up.
+        if path.ends_with(".dbg.json") {
+            if !quiet {
+                println!("Skipping {}, a Hardhat debug file", path);
+            }
+            continue;
+        }
+        let mut input = Input::new(path.clone(), name.clone());
+        if let Err(err) = process(&options, &mut input, &mut sink) {
+            eprintln!("error: {}", err);
+            failed += 1;
+            if !keep_going {
+                process::exit(1);
+            }
+        }
+        inputs.push(input);
+    }
+    if let Err(err) = finish(&options, &inputs, true, &mut sink) {
+        eprintln!("error: {}", err);
+        if !options.watch {
+            process::exit(1);
+        }
+    }
+    if options.watch {
+        watch(&options, inputs);
+    }
+    if let Sink::Check { expected, diffs } = &mut sink {
+        // The outputs of failed inputs are unknown, so none is orphaned, and
+        // signatures carry no header telling them apart.
+        let orphans = if failed > 0 || options.human_readable {
+            Ok(vec![])
+        } else {
+            check::orphans(&options.out_dir, expected)
+        };
+        match orphans {
+            Ok(orphans) => diffs.extend(orphans.iter().map(|orphan| {
+                let before = check::read_existing(orphan).ok().flatten();
+                check::diff(orphan, before.as_deref(), None)
+            })),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        }
+        for diff in diffs.iter() {
+            print!("{}", diff);
+        }
+    }
+    if failed > 0 {
+        eprintln!("{} of {} inputs failed", failed, options.inputs.len());
+        process::exit(1);
+    }
+    if sink.stale() > 0 {
+        eprintln!(
+            "{} generated files are stale, missing or orphaned, regenerate them",
+            sink.stale()
+        );
+        process::exit(1);
+    }
+    if !quiet {
+        println!("All done!");
+    }
 }
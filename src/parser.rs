@@ -1,100 +1,400 @@
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
-#[serde(rename_all = "lowercase")]
+use crate::{error::Error, human_readable::parse_abi};
+
+/// A Solidity type, as found in the `type` and `internalType` fields of
+/// the ABI.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DataType {
-    UInt256,
-    String,
+    /// `uintN`, holding the width in bits.
+    UInt(u16),
+    /// `intN`, holding the width in bits.
+    Int(u16),
     Address,
-    UInt8,
     Bool,
-    #[serde(alias = "uint8[]")]
-    UInt8Array,
-    #[serde(alias = "uint256[]")]
-    UInt256Array,
+    String,
+    /// Dynamically sized `bytes`.
+    Bytes,
+    /// `bytesN`, holding the size in bytes.
+    FixedBytes(u8),
+    /// `fixedMxN`, holding the width in bits and the decimal places.
+    Fixed(u16, u8),
+    /// `ufixedMxN`, holding the width in bits and the decimal places.
+    UFixed(u16, u8),
+    Function,
+    /// `T[]` when the size is `None`, or `T[k]` otherwise.
+    Array(Box<DataType>, Option<usize>),
+    /// A `tuple`, whose fields are found in the `components` of the
+    /// parameter.
+    Tuple,
     Contract(String),
     Enum(String),
+    /// The qualified name of a struct, like `Dao.Proposal`.
+    Struct(String),
     Other(String),
 }
 
-fn deserialize_data_type<'de, D>(deserializer: D) -> Result<DataType, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    struct DataTypeVisitor;
-    impl<'de> serde::de::Visitor<'de> for DataTypeVisitor {
-        type Value = DataType;
+/// Parses the `N` of `uintN` and `intN`, where an empty width means 256.
+fn parse_int_width(ty: &str, width: &str) -> Result<u16, String> {
+    if width.is_empty() {
+        return Ok(256);
+    }
+    match width.parse::<u16>() {
+        Ok(bits) if bits % 8 == 0 && (8..=256).contains(&bits) => Ok(bits),
+        _ => Err(format!("invalid integer width in `{}`", ty)),
+    }
+}
 
-        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-        where
-            E: serde::de::Error,
+/// Parses the `MxN` of `fixedMxN` and `ufixedMxN`, where an empty suffix
+/// means `128x18`.
+fn parse_fixed_size(ty: &str, size: &str) -> Result<(u16, u8), String> {
+    if size.is_empty() {
+        return Ok((128, 18));
+    }
+    let invalid = || format!("invalid fixed point size in `{}`", ty);
+    let (bits, decimals) = size.split_once('x').ok_or_else(invalid)?;
+    match (bits.parse::<u16>(), decimals.parse::<u8>()) {
+        (Ok(bits), Ok(decimals))
+            if bits % 8 == 0 && (8..=256).contains(&bits) && decimals <= 80 =>
         {
-            Ok(match v {
-                "uint256" => DataType::UInt256,
-                "string" => DataType::String,
-                "address" => DataType::Address,
-                "uint8" => DataType::UInt8,
-                other => {
-                    if other.starts_with("contract") {
-                        DataType::Contract(other.replace("contract ", ""))
-                    } else if other.starts_with("enum") {
-                        DataType::Enum(other.replace("enum ", ""))
-                    } else {
-                        DataType::Other(other.into())
+            Ok((bits, decimals))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+impl FromStr for DataType {
+    type Err = String;
+
+    fn from_str(ty: &str) -> Result<Self, Self::Err> {
+        // The outermost dimension is the last one: `string[2][]` is a
+        // dynamic array of `string[2]`.
+        if let Some(dimensions) = ty.strip_suffix(']') {
+            let (inner, size) = dimensions
+                .rsplit_once('[')
+                .ok_or_else(|| format!("unbalanced brackets in `{}`", ty))?;
+            let size = if size.is_empty() {
+                None
+            } else {
+                match size.parse::<usize>() {
+                    Ok(size) if size > 0 => Some(size),
+                    _ => return Err(format!("invalid array size in `{}`", ty)),
+                }
+            };
+            return Ok(DataType::Array(Box::new(inner.parse()?), size));
+        }
+        if let Some(name) = ty.strip_prefix("contract ") {
+            return Ok(DataType::Contract(name.into()));
+        }
+        if let Some(name) = ty.strip_prefix("enum ") {
+            return Ok(DataType::Enum(name.into()));
+        }
+        if let Some(name) = ty.strip_prefix("struct ") {
+            return Ok(DataType::Struct(name.into()));
+        }
+        Ok(match ty {
+            "address" | "address payable" => DataType::Address,
+            "bool" => DataType::Bool,
+            "string" => DataType::String,
+            "bytes" => DataType::Bytes,
+            "byte" => DataType::FixedBytes(1),
+            "function" => DataType::Function,
+            "tuple" => DataType::Tuple,
+            other => {
+                if let Some(width) = other.strip_prefix("uint") {
+                    DataType::UInt(parse_int_width(other, width)?)
+                } else if let Some(width) = other.strip_prefix("int") {
+                    DataType::Int(parse_int_width(other, width)?)
+                } else if let Some(size) = other.strip_prefix("ufixed") {
+                    let (bits, decimals) = parse_fixed_size(other, size)?;
+                    DataType::UFixed(bits, decimals)
+                } else if let Some(size) = other.strip_prefix("fixed") {
+                    let (bits, decimals) = parse_fixed_size(other, size)?;
+                    DataType::Fixed(bits, decimals)
+                } else if let Some(size) = other.strip_prefix("bytes") {
+                    match size.parse::<u8>() {
+                        Ok(size) if (1..=32).contains(&size) => DataType::FixedBytes(size),
+                        _ => return Err(format!("invalid byte array size in `{}`", other)),
                     }
+                } else {
+                    DataType::Other(other.into())
                 }
-            })
+            }
+        })
+    }
+}
+
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataType::UInt(bits) => write!(f, "uint{}", bits),
+            DataType::Int(bits) => write!(f, "int{}", bits),
+            DataType::Address => f.write_str("address"),
+            DataType::Bool => f.write_str("bool"),
+            DataType::String => f.write_str("string"),
+            DataType::Bytes => f.write_str("bytes"),
+            DataType::FixedBytes(size) => write!(f, "bytes{}", size),
+            DataType::Fixed(bits, decimals) => write!(f, "fixed{}x{}", bits, decimals),
+            DataType::UFixed(bits, decimals) => write!(f, "ufixed{}x{}", bits, decimals),
+            DataType::Function => f.write_str("function"),
+            DataType::Array(inner, Some(size)) => write!(f, "{}[{}]", inner, size),
+            DataType::Array(inner, None) => write!(f, "{}[]", inner),
+            DataType::Contract(name) => write!(f, "contract {}", name),
+            DataType::Tuple => f.write_str("tuple"),
+            DataType::Enum(name) => write!(f, "enum {}", name),
+            DataType::Struct(name) => write!(f, "struct {}", name),
+            DataType::Other(other) => f.write_str(other),
         }
+    }
+}
+
+impl Serialize for DataType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DataType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct DataTypeVisitor;
+        impl<'de> serde::de::Visitor<'de> for DataTypeVisitor {
+            type Value = DataType;
 
-        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-            formatter.write_str("string, uint256, address, uint8 or an internal type")
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                v.parse().map_err(E::custom)
+            }
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a solidity type name")
+            }
         }
+        deserializer.deserialize_str(DataTypeVisitor)
+    }
+}
+
+/// A parameter with every field any compiler writes, which solc before
+/// 0.5.11 and Vyper lack `internalType` from.
+#[derive(Debug, Deserialize)]
+struct RawParam {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type")]
+    io_type: DataType,
+    #[serde(rename = "internalType")]
+    internal_type: Option<DataType>,
+    #[serde(default)]
+    components: Vec<FuncIO>,
+    #[serde(default)]
+    indexed: bool,
+}
+
+impl RawParam {
+    /// The internal type, which is the ABI type itself when missing.
+    fn internal_type(&self) -> DataType {
+        self.internal_type
+            .clone()
+            .unwrap_or_else(|| self.io_type.clone())
     }
-    deserializer.deserialize_any(DataTypeVisitor)
 }
 
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(from = "RawParam")]
 pub struct EventInput {
     pub indexed: bool,
-    #[serde(alias = "internalType", deserialize_with = "deserialize_data_type")]
+    #[serde(rename = "internalType")]
     pub internal_type: DataType,
     pub name: String,
-    #[serde(alias = "type")]
+    #[serde(rename = "type")]
     pub input_type: DataType,
+    pub components: Vec<FuncIO>,
+}
+
+impl EventInput {
+    pub fn canonical_type(&self) -> String {
+        canonical_type(&self.input_type, &self.components)
+    }
+}
+
+impl From<RawParam> for EventInput {
+    fn from(param: RawParam) -> Self {
+        EventInput {
+            indexed: param.indexed,
+            internal_type: param.internal_type(),
+            name: param.name,
+            input_type: param.io_type,
+            components: param.components,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(from = "RawParam")]
 pub struct CtorInput {
     pub name: String,
-    #[serde(alias = "internalType")]
+    #[serde(rename = "internalType")]
     pub internal_type: DataType,
-    #[serde(alias = "type")]
+    #[serde(rename = "type")]
     pub input_type: DataType,
+    pub components: Vec<FuncIO>,
+}
+
+impl From<RawParam> for CtorInput {
+    fn from(param: RawParam) -> Self {
+        CtorInput {
+            internal_type: param.internal_type(),
+            name: param.name,
+            input_type: param.io_type,
+            components: param.components,
+        }
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum StateMutability {
     NonPayable,
+    Payable,
     View,
     Pure,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(from = "RawParam")]
 pub struct FuncIO {
     pub name: String,
-    #[serde(alias = "type")]
+    #[serde(rename = "type")]
     pub io_type: DataType,
-    #[serde(alias = "internalType", deserialize_with = "deserialize_data_type")]
+    #[serde(rename = "internalType")]
     pub internal_type: DataType,
+    pub components: Vec<FuncIO>,
+}
+
+impl From<RawParam> for FuncIO {
+    fn from(param: RawParam) -> Self {
+        FuncIO {
+            internal_type: param.internal_type(),
+            name: param.name,
+            io_type: param.io_type,
+            components: param.components,
+        }
+    }
+}
+
+impl FuncIO {
+    /// The type as written in signatures, where tuples are spelled out as
+    /// the list of their components.
+    pub fn canonical_type(&self) -> String {
+        canonical_type(&self.io_type, &self.components)
+    }
+}
+
+fn canonical_type(io_type: &DataType, components: &[FuncIO]) -> String {
+    match io_type {
+        DataType::Array(inner, Some(size)) => {
+            format!("{}[{}]", canonical_type(inner, components), size)
+        }
+        DataType::Array(inner, None) => format!("{}[]", canonical_type(inner, components)),
+        DataType::Tuple => format!(
+            "({})",
+            components
+                .iter()
+                .map(FuncIO::canonical_type)
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+        other => other.to_string(),
+    }
+}
+
+/// The signature of a function or error, like `transfer(address,uint256)`,
+/// which tells overloads apart.
+pub fn signature(name: &str, inputs: &[FuncIO]) -> String {
+    format!(
+        "{}({})",
+        name,
+        inputs
+            .iter()
+            .map(FuncIO::canonical_type)
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+}
+
+/// The signature of an event, like `Transfer(address,address,uint256)`,
+/// which tells overloads apart.
+pub fn event_signature(name: &str, inputs: &[EventInput]) -> String {
+    format!(
+        "{}({})",
+        name,
+        inputs
+            .iter()
+            .map(EventInput::canonical_type)
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+}
+
+/// An ABI entry with every field any compiler writes, before telling its
+/// kind apart.
+#[derive(Debug, Deserialize)]
+struct RawAbiEntry {
+    /// Which solc before 0.4.16 may leave out for functions.
+    #[serde(rename = "type", default = "RawAbiEntry::function")]
+    kind: String,
+    name: Option<String>,
+    #[serde(default)]
+    inputs: Vec<RawParam>,
+    #[serde(default)]
+    outputs: Vec<FuncIO>,
+    #[serde(rename = "stateMutability")]
+    state_mutability: Option<StateMutability>,
+    /// Written instead of the mutability by solc before 0.4.16 and Vyper.
+    #[serde(default)]
+    constant: bool,
+    #[serde(default)]
+    payable: bool,
+    #[serde(default)]
+    anonymous: bool,
+}
+
+impl RawAbiEntry {
+    fn function() -> String {
+        "function".into()
+    }
+    /// The mutability of the entry, derived from the legacy flags when
+    /// the compiler didn't write it.
+    fn mutability(&self) -> StateMutability {
+        match (&self.state_mutability, self.constant, self.payable) {
+            (Some(mutability), _, _) => mutability.clone(),
+            (None, true, _) => StateMutability::View,
+            (None, false, true) => StateMutability::Payable,
+            (None, false, false) => StateMutability::NonPayable,
+        }
+    }
+    fn name(&mut self) -> Result<String, String> {
+        self.name
+            .take()
+            .ok_or_else(|| format!("missing field `name` in {} entry", self.kind))
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
-#[serde(tag = "type", rename_all = "lowercase")]
+#[serde(tag = "type", rename_all = "lowercase", try_from = "RawAbiEntry")]
 pub enum AbiEntry {
     Constructor {
         inputs: Vec<CtorInput>,
-        #[serde(alias = "stateMutability")]
+        #[serde(rename = "stateMutability")]
         mutability: StateMutability,
     },
     Event {
@@ -104,24 +404,357 @@ pub enum AbiEntry {
     },
     Function {
         name: String,
-        #[serde(alias = "stateMutability")]
+        #[serde(rename = "stateMutability")]
         mutability: StateMutability,
-        #[serde(default)]
         constant: bool,
         inputs: Vec<FuncIO>,
         outputs: Vec<FuncIO>,
     },
+    Error {
+        name: String,
+        inputs: Vec<FuncIO>,
+    },
+    Fallback {
+        #[serde(rename = "stateMutability")]
+        mutability: StateMutability,
+    },
+    Receive {
+        #[serde(rename = "stateMutability")]
+        mutability: StateMutability,
+    },
+}
+
+impl TryFrom<RawAbiEntry> for AbiEntry {
+    type Error = String;
+
+    fn try_from(mut entry: RawAbiEntry) -> Result<Self, String> {
+        let mutability = entry.mutability();
+        Ok(match entry.kind.as_str() {
+            "constructor" => AbiEntry::Constructor {
+                inputs: entry.inputs.into_iter().map(CtorInput::from).collect(),
+                mutability,
+            },
+            "event" => AbiEntry::Event {
+                name: entry.name()?,
+                anonymous: entry.anonymous,
+                inputs: entry.inputs.into_iter().map(EventInput::from).collect(),
+            },
+            "function" => AbiEntry::Function {
+                name: entry.name()?,
+                mutability,
+                constant: entry.constant,
+                inputs: entry.inputs.into_iter().map(FuncIO::from).collect(),
+                outputs: entry.outputs,
+            },
+            "error" => AbiEntry::Error {
+                name: entry.name()?,
+                inputs: entry.inputs.into_iter().map(FuncIO::from).collect(),
+            },
+            "fallback" => AbiEntry::Fallback { mutability },
+            "receive" => AbiEntry::Receive { mutability },
+            other => return Err(format!("unknown ABI entry type `{}`", other)),
+        })
+    }
+}
+
+/// Where a library address has to be written into the bytecode before it
+/// can be deployed, as an offset and length in bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LinkReference {
+    pub start: usize,
+    pub length: usize,
+}
+
+/// The placeholders of the libraries a bytecode is linked against, by
+/// source file and library name.
+pub type LinkReferences = BTreeMap<String, BTreeMap<String, Vec<LinkReference>>>;
+
+/// The compilation artifact layouts that can be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Truffle's `build/contracts/*.json`.
+    Truffle,
+    /// Hardhat's `artifacts/**/*.json`, tagged as `hh-sol-artifact-1`.
+    Hardhat,
+    /// Foundry's `out/*.sol/*.json`, whose bytecode is an object.
+    Foundry,
+    /// A bare ABI, as a list of entries with nothing around it.
+    Abi,
+    /// A list of human-readable signatures, like
+    /// `function balanceOf(address owner) view returns (uint256)`.
+    HumanReadable,
+    /// The output of `solc --standard-json`, holding many contracts.
+    StandardJson,
+    /// Hardhat's `artifacts/build-info/*.json`, which wrap the standard JSON
+    /// output.
+    BuildInfo,
+}
+
+impl Format {
+    /// Tells the layout of an artifact apart from the fields it holds.
+    pub fn detect(str: &str) -> Result<Format, Error> {
+        if let Some(entries) = str.trim_start().strip_prefix('[') {
+            return Ok(if entries.trim_start().starts_with('"') {
+                Format::HumanReadable
+            } else {
+                Format::Abi
+            });
+        }
+        #[derive(Deserialize)]
+        struct Probe {
+            #[serde(rename = "_format")]
+            format: Option<String>,
+            bytecode: Option<serde_json::Value>,
+            contracts: Option<serde::de::IgnoredAny>,
+            errors: Option<serde::de::IgnoredAny>,
+        }
+        let probe: Probe = from_json(str)?;
+        match probe.format.as_deref() {
+            None if matches!(probe.bytecode, Some(serde_json::Value::Object(_))) => {
+                Ok(Format::Foundry)
+            }
+            None if probe.contracts.is_some() || probe.errors.is_some() => Ok(Format::StandardJson),
+            None => Ok(Format::Truffle),
+            Some("hh-sol-artifact-1") => Ok(Format::Hardhat),
+            Some("hh-sol-build-info-1") => Ok(Format::BuildInfo),
+            Some(format) if format.starts_with("hh-sol-dbg") => Err(Error::parse(
+                "found a Hardhat debug file, which holds no contract",
+            )),
+            Some(format) => Err(Error::parse(format!(
+                "unknown artifact format `{}`",
+                format
+            ))),
+        }
+    }
 }
 
+/// A compiled contract, whichever the layout of the artifact it came from.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Contract {
     pub abi: Vec<AbiEntry>,
-    #[serde(alias = "contractName")]
+    #[serde(alias = "contractName", alias = "contract_name")]
     pub name: String,
+    /// The file declaring the contract, like `contracts/Vault.sol`.
+    #[serde(alias = "sourceName", default)]
+    pub source_name: Option<String>,
+    /// The creation bytecode, which abstract contracts and interfaces lack.
+    /// Truffle before v5 named it `unlinked_binary`.
+    #[serde(alias = "unlinked_binary", default)]
+    pub bytecode: Option<String>,
+    /// The bytecode stored on chain once deployed.
+    #[serde(alias = "deployedBytecode", default)]
+    pub deployed_bytecode: Option<String>,
+    #[serde(alias = "linkReferences", default)]
+    pub link_references: LinkReferences,
+    #[serde(alias = "deployedLinkReferences", default)]
+    pub deployed_link_references: LinkReferences,
+    /// The selectors of the functions, in hex, by signature.
+    #[serde(alias = "methodIdentifiers", default)]
+    pub method_identifiers: BTreeMap<String, String>,
 }
 
 impl Contract {
-    pub fn from_str(str: &str) -> serde_json::error::Result<Contract> {
-        serde_json::from_str(str)
+    /// The name qualified by its source, like `contracts/Vault.sol:Vault`,
+    /// when known.
+    pub fn qualified_name(&self) -> String {
+        match &self.source_name {
+            Some(source) => format!("{}:{}", source, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// A bytecode as solc and Foundry write it, along with its link
+/// references.
+#[derive(Debug, Deserialize)]
+struct BytecodeObject {
+    object: String,
+    #[serde(rename = "linkReferences", default)]
+    link_references: LinkReferences,
+}
+
+impl BytecodeObject {
+    /// Splits a bytecode into its hex string, which solc writes without the
+    /// `0x` prefix, and its link references.
+    fn split(bytecode: Option<BytecodeObject>) -> (Option<String>, LinkReferences) {
+        match bytecode {
+            Some(BytecodeObject {
+                object,
+                link_references,
+            }) if object.is_empty() || object.starts_with("0x") => (Some(object), link_references),
+            Some(BytecodeObject {
+                object,
+                link_references,
+            }) => (Some(format!("0x{}", object)), link_references),
+            None => (None, LinkReferences::new()),
+        }
+    }
+}
+
+/// Foundry artifacts don't name the contract, which is found in the
+/// compilation target of the metadata instead.
+#[derive(Debug, Deserialize)]
+struct FoundryArtifact {
+    abi: Vec<AbiEntry>,
+    bytecode: Option<BytecodeObject>,
+    #[serde(rename = "deployedBytecode")]
+    deployed_bytecode: Option<BytecodeObject>,
+    #[serde(rename = "methodIdentifiers", default)]
+    method_identifiers: BTreeMap<String, String>,
+    #[serde(default)]
+    metadata: serde_json::Value,
+}
+
+impl FoundryArtifact {
+    fn into_contract(self, fallback_name: Option<&str>) -> Result<Contract, Error> {
+        let target = self
+            .metadata
+            .pointer("/settings/compilationTarget")
+            .and_then(serde_json::Value::as_object)
+            .and_then(|target| target.iter().next());
+        let (source_name, name) = match target {
+            Some((source, name)) => (Some(source.clone()), name.as_str().map(String::from)),
+            None => (None, None),
+        };
+        let name = name
+            .or_else(|| fallback_name.map(String::from))
+            .ok_or_else(|| Error::parse("the artifact names no contract, and no name was given"))?;
+        let (bytecode, link_references) = BytecodeObject::split(self.bytecode);
+        let (deployed_bytecode, deployed_link_references) =
+            BytecodeObject::split(self.deployed_bytecode);
+        Ok(Contract {
+            abi: self.abi,
+            name,
+            source_name,
+            bytecode,
+            deployed_bytecode,
+            link_references,
+            deployed_link_references,
+            method_identifiers: self.method_identifiers,
+        })
+    }
+}
+
+/// The output of `solc --standard-json`, holding every contract of every
+/// source file.
+#[derive(Debug, Deserialize)]
+struct StandardJsonOutput {
+    #[serde(default)]
+    errors: Vec<StandardJsonError>,
+    #[serde(default)]
+    contracts: BTreeMap<String, BTreeMap<String, StandardJsonContract>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StandardJsonError {
+    severity: String,
+    message: String,
+    #[serde(rename = "formattedMessage")]
+    formatted_message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StandardJsonContract {
+    #[serde(default)]
+    abi: Vec<AbiEntry>,
+    #[serde(default)]
+    evm: StandardJsonEvm,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct StandardJsonEvm {
+    bytecode: Option<BytecodeObject>,
+    #[serde(rename = "deployedBytecode")]
+    deployed_bytecode: Option<BytecodeObject>,
+    #[serde(rename = "methodIdentifiers", default)]
+    method_identifiers: BTreeMap<String, String>,
+}
+
+/// Hardhat's `build-info` files, which keep the standard JSON output next
+/// to the input it was compiled from.
+#[derive(Debug, Deserialize)]
+struct BuildInfo {
+    output: StandardJsonOutput,
+}
+
+impl StandardJsonOutput {
+    fn into_contracts(self) -> Result<Vec<Contract>, Error> {
+        // Warnings come along with the contracts, but errors leave none.
+        if let Some(error) = self.errors.iter().find(|error| error.severity == "error") {
+            return Err(Error::parse(format!(
+                "the compilation failed: {}",
+                error
+                    .formatted_message
+                    .as_ref()
+                    .unwrap_or(&error.message)
+                    .trim_end()
+            )));
+        }
+        Ok(self
+            .contracts
+            .into_iter()
+            .flat_map(|(source_name, contracts)| {
+                contracts.into_iter().map(move |(name, contract)| {
+                    let (bytecode, link_references) = BytecodeObject::split(contract.evm.bytecode);
+                    let (deployed_bytecode, deployed_link_references) =
+                        BytecodeObject::split(contract.evm.deployed_bytecode);
+                    Contract {
+                        abi: contract.abi,
+                        name,
+                        source_name: Some(source_name.clone()),
+                        bytecode,
+                        deployed_bytecode,
+                        link_references,
+                        deployed_link_references,
+                        method_identifiers: contract.evm.method_identifiers,
+                    }
+                })
+            })
+            .collect())
+    }
+}
+
+/// Deserializes JSON, keeping track of the path to the value that fails.
+fn from_json<'de, T>(str: &'de str) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    let deserializer = &mut serde_json::Deserializer::from_str(str);
+    Ok(serde_path_to_error::deserialize(deserializer)?)
+}
+
+/// Reads every contract of an artifact of any known format, naming the
+/// contract after `fallback_name`, usually the file stem, when the artifact
+/// doesn't.
+pub fn read_artifact(str: &str, fallback_name: Option<&str>) -> Result<Vec<Contract>, Error> {
+    let format = Format::detect(str)?;
+    match format {
+        Format::Truffle | Format::Hardhat => Ok(vec![from_json(str)?]),
+        Format::Foundry => Ok(vec![
+            from_json::<FoundryArtifact>(str)?.into_contract(fallback_name)?
+        ]),
+        Format::Abi | Format::HumanReadable => {
+            let abi = if format == Format::Abi {
+                from_json(str)?
+            } else {
+                let signatures: Vec<String> = from_json(str)?;
+                parse_abi(&signatures).map_err(Error::parse)?
+            };
+            let name = fallback_name.ok_or_else(|| {
+                Error::parse("a bare ABI names no contract, and no name was given")
+            })?;
+            Ok(vec![Contract {
+                abi,
+                name: name.into(),
+                source_name: None,
+                bytecode: None,
+                deployed_bytecode: None,
+                link_references: LinkReferences::new(),
+                deployed_link_references: LinkReferences::new(),
+                method_identifiers: BTreeMap::new(),
+            }])
+        }
+        Format::StandardJson => from_json::<StandardJsonOutput>(str)?.into_contracts(),
+        Format::BuildInfo => from_json::<BuildInfo>(str)?.output.into_contracts(),
     }
 }
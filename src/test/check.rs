@@ -0,0 +1,58 @@
+use std::path::{Path, PathBuf};
+
+use crate::check::{diff, orphans, read_existing};
+
+#[test]
+fn diff_outputs_against_the_disk() {
+    let path = Path::new("gen/Token.ts");
+    assert_eq!(
+        "\
+--- gen/Token.ts
++++ gen/Token.ts
+@@ -1,2 +1,2 @@
+ export default class Token {
+-}
++  }
+",
+        diff(
+            path,
+            Some("export default class Token {\n}\n"),
+            Some("export default class Token {\n  }\n")
+        )
+    );
+    assert_eq!(
+        "--- /dev/null\n+++ gen/Token.ts\n@@ -0,0 +1 @@\n+export {};\n",
+        diff(path, None, Some("export {};\n"))
+    );
+    assert_eq!(
+        "--- gen/Token.ts\n+++ /dev/null\n@@ -1 +0,0 @@\n-export {};\n",
+        diff(path, Some("export {};\n"), None)
+    );
+}
+
+#[test]
+fn find_the_generated_files_no_longer_expected() {
+    let dir = Path::new("src/test/fixtures/check");
+    assert_eq!(
+        vec![dir.join("nested/Old.ts")],
+        orphans(dir, &[dir.join("Kept.ts")]).unwrap()
+    );
+    assert_eq!(
+        Vec::<PathBuf>::new(),
+        orphans(&dir.join("gone"), &[]).unwrap()
+    );
+    assert_eq!(None, read_existing(&dir.join("Gone.ts")).unwrap());
+}
+
+#[test]
+fn find_orphans_in_the_working_directory() {
+    // Without `--out-dir`, outputs go to the working directory.
+    assert_eq!(
+        vec![PathBuf::from("src/test/fixtures/check/nested/Old.ts")],
+        orphans(
+            Path::new(""),
+            &[PathBuf::from("./src/test/fixtures/check/Kept.ts")]
+        )
+        .unwrap()
+    );
+}
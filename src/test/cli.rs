@@ -0,0 +1,268 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    cli::{expand_inputs, parse_args, Command, Options, Verbosity},
+    code_emitter::{Case, NumericMapping, Target},
+    config::Config,
+    parser::{read_artifact, signature, AbiEntry, Contract},
+};
+
+fn run(args: &[&str]) -> Options {
+    match parse_args(args.iter().map(|arg| arg.to_string())) {
+        Ok(Command::Run(options)) => *options,
+        Ok(_) => panic!("expected to run"),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+#[test]
+fn expand_glob_inputs() {
+    let options = run(&[
+        "--name",
+        "Token",
+        "src/test/fixtures/Token.abi.json",
+        "src/test/fixtures/hardhat/*.json",
+    ]);
+    assert_eq!(
+        vec![
+            (
+                "src/test/fixtures/Token.abi.json".to_owned(),
+                Some("Token".to_owned())
+            ),
+            (
+                "src/test/fixtures/hardhat/Counter.dbg.json".to_owned(),
+                None
+            ),
+            ("src/test/fixtures/hardhat/Counter.json".to_owned(), None),
+        ],
+        options.inputs
+    );
+}
+
+#[test]
+fn keep_the_directories_below_the_root() {
+    let options = run(&[
+        "-o",
+        "out",
+        "--root",
+        "src/test/fixtures",
+        "-q",
+        "src/test/fixtures/Token.json",
+    ]);
+    assert_eq!(Verbosity::Quiet, options.verbosity);
+    assert_eq!(
+        PathBuf::from("out"),
+        options.out_dir_of("src/test/fixtures/Token.json").unwrap()
+    );
+    assert_eq!(
+        PathBuf::from("out/foundry"),
+        options
+            .out_dir_of("./src/test/fixtures/foundry/Counter.json")
+            .unwrap()
+    );
+    assert_eq!(
+        "./AbstractContract",
+        options
+            .abstraction_import_of("src/test/fixtures/Token.json")
+            .unwrap()
+    );
+    assert_eq!(
+        "../../AbstractContract",
+        options
+            .abstraction_import_of("src/test/fixtures/solc/nested/A.json")
+            .unwrap()
+    );
+    assert!(options.out_dir_of("elsewhere/A.json").is_err());
+}
+
+#[test]
+fn read_options_from_a_config() {
+    let options = run(&["--config", "src/test/fixtures/config/spider-jockey.toml"]);
+    assert_eq!(Target::EthersV6, options.emitter.target);
+    assert_eq!(
+        PathBuf::from("src/test/fixtures/config/generated"),
+        options.out_dir
+    );
+    assert_eq!(
+        Some(PathBuf::from("src/test/fixtures/config/..")),
+        options.root
+    );
+    assert_eq!(2, options.inputs.len());
+    // The command line wins over the config.
+    let options = run(&[
+        "--config=src/test/fixtures/config/spider-jockey.toml",
+        "--target=generic",
+        "src/test/fixtures/Token.json",
+    ]);
+    assert_eq!(Target::Generic, options.emitter.target);
+    assert_eq!(
+        vec![("src/test/fixtures/Token.json".to_owned(), None)],
+        options.inputs
+    );
+}
+
+#[test]
+fn reject_bad_arguments() {
+    let err = |args: &[&str]| match parse_args(args.iter().map(|arg| arg.to_string())) {
+        Err(err) => err.to_string(),
+        Ok(_) => panic!("expected an error"),
+    };
+    assert_eq!("unknown option `--bogus`", err(&["--bogus", "A.json"]));
+    assert_eq!(
+        "missing the value of `--out-dir`",
+        err(&["A.json", "--out-dir"])
+    );
+    assert_eq!("no inputs given, see `--help` for the usage", err(&[]));
+    assert_eq!(
+        "`src/test/fixtures/*.sol` matches no file",
+        err(&["src/test/fixtures/*.sol"])
+    );
+    assert_eq!(
+        "`--name` can't name the many files of `src/test/fixtures/*.json`",
+        err(&["--name", "A", "src/test/fixtures/*.json"])
+    );
+    assert_eq!(
+        "unknown target `ethers`, expected `generic`, `ethers-v5` or `ethers-v6`",
+        err(&["--target", "ethers", "A.json"])
+    );
+    assert_eq!(
+        "`--bundle` and `--human-readable` don't go together",
+        err(&["--human-readable", "--bundle", "all.ts", "A.json"])
+    );
+    assert_eq!(
+        "`--bundle` can't apply the numeric mapping and method naming of `overrides.Vault`, which only go with a file per contract",
+        err(&[
+            "-c",
+            "src/test/fixtures/config/nested/.spiderjockeyrc",
+            "--bundle",
+            "all.ts"
+        ])
+    );
+    assert_eq!(
+        "`ethers-v6` decodes integers as `bigint`, which can't be typed as `BigNumber`",
+        err(&["-t", "ethers-v6", "--numeric", "BigNumber:ethers", "A.json"])
+    );
+    assert_eq!(
+        "`overrides.Token` types integers as `BigNumber`, but the ethers runtime shared by every contract decodes them as `bigint`",
+        err(&["-c", "src/test/fixtures/config/ethers-v5.toml"])
+    );
+    // Contracts implementing the abstraction themselves can type them anyhow.
+    run(&[
+        "-c",
+        "src/test/fixtures/config/ethers-v5.toml",
+        "-t",
+        "generic",
+    ]);
+    assert_eq!(
+        "`--check` and `--watch` don't go together",
+        err(&["--check", "--watch", "A.json"])
+    );
+    assert!(matches!(
+        parse_args(["-h".to_owned()].into_iter()),
+        Ok(Command::Help)
+    ));
+}
+
+#[test]
+fn discover_configs_up_from_a_directory() {
+    assert_eq!(
+        Some(PathBuf::from("spider-jockey.toml")),
+        Config::discover(Path::new("src/test/fixtures/config"))
+    );
+    assert_eq!(
+        Some(PathBuf::from(".spiderjockeyrc")),
+        Config::discover(Path::new("src/test/fixtures/config/nested"))
+    );
+}
+
+#[test]
+fn apply_the_settings_of_contracts() {
+    let options = run(&["-c", "src/test/fixtures/config/nested/.spiderjockeyrc"]);
+    let contracts = options
+        .inputs
+        .iter()
+        .flat_map(|(input, _)| read_artifact(&fs::read_to_string(input).unwrap(), None).unwrap());
+    let contracts = options.select(contracts.collect());
+    let functions = |contract: &Contract| {
+        contract
+            .abi
+            .iter()
+            .filter_map(|entry| match entry {
+                AbiEntry::Function { name, inputs, .. } => Some(signature(name, inputs)),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(2, contracts.len());
+    let (nft, vault) = (&contracts[0], &contracts[1]);
+    assert_eq!(
+        vec![
+            "safeTransferFrom(address,address,uint256)",
+            "safeTransferFrom(address,address,uint256,bytes)",
+            "mint(address)",
+        ],
+        functions(nft)
+    );
+    assert_eq!(vec!["balanceOf(address)"], functions(vault));
+    assert_eq!("nft", options.file_name_of(nft));
+    assert_eq!(Case::Snake, options.emitter_of(nft).methods);
+    assert_eq!(
+        NumericMapping::BigNumberish,
+        options.emitter_of(nft).numeric
+    );
+    assert_eq!(Case::Preserve, options.emitter_of(vault).methods);
+    assert_eq!(NumericMapping::BigInt, options.emitter_of(vault).numeric);
+}
+
+#[test]
+fn case_names() {
+    let cases = [Case::Camel, Case::Pascal, Case::Snake, Case::Kebab];
+    let apply = |name: &str| cases.map(|case| case.apply(name));
+    assert_eq!(["getDy", "GetDy", "get_dy", "get-dy"], apply("get_dy"));
+    assert_eq!(
+        ["erc20Token", "Erc20Token", "erc20_token", "erc20-token"],
+        apply("ERC20Token")
+    );
+    assert_eq!(
+        ["_balanceOf", "_BalanceOf", "_balance_of", "_balance-of"],
+        apply("_balanceOf")
+    );
+    assert_eq!("ERC20Token", Case::Preserve.apply("ERC20Token"));
+}
+
+#[test]
+fn locate_config_errors() {
+    let err = |contents: &str| {
+        Config::read(Path::new("spider-jockey.toml"), contents)
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(
+        "spider-jockey.toml:2:10: invalid type: integer `3`, expected a sequence",
+        err("target = \"generic\"\ninputs = 3\n")
+    );
+    assert_eq!(
+        "spider-jockey.toml: bad pattern `[a`: Pattern syntax error near position 0: invalid range pattern",
+        err("[contracts]\nexclude = [\"[a\"]\n")
+    );
+}
+
+#[test]
+fn expand_the_patterns_again_when_watching() {
+    let options = run(&["--watch", "src/test/fixtures/hardhat/*.json"]);
+    assert!(options.watch);
+    assert_eq!(
+        vec![("src/test/fixtures/hardhat/*.json".to_owned(), None)],
+        options.patterns
+    );
+    // Watched patterns may match nothing for a while.
+    let patterns = [("src/test/fixtures/*.sol".to_owned(), None)];
+    assert_eq!(
+        Vec::<(String, Option<String>)>::new(),
+        expand_inputs(&patterns, false).unwrap()
+    );
+    assert!(expand_inputs(&patterns, true).is_err());
+}
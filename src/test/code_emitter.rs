@@ -0,0 +1,781 @@
+use crate::{
+    code_emitter::{CodeEmitter, NumericMapping, Target},
+    parser::read_artifact,
+};
+
+#[test]
+fn emit_structs_and_return_types() {
+    let contract = read_artifact(include_str!("fixtures/Dao.json"), None)
+        .unwrap()
+        .remove(0);
+    let out = CodeEmitter::default()
+        .emit(&contract, "./AbstractContract")
+        .unwrap();
+    assert_eq!(
+        "
+import AbstractContract, { ContractTransaction, Overrides } from \"./AbstractContract\";
+export interface Window {
+  start: bigint | number | string;
+  end: bigint | number | string;
+}
+export interface WindowOutput {
+  start: bigint;
+  end: bigint;
+}
+export interface Proposal {
+  title: string;
+  voters: Array<string>;
+  window: Window;
+}
+export interface ProposalOutput {
+  title: string;
+  voters: Array<string>;
+  window: WindowOutput;
+}
+export default class Dao {
+  constructor(private readonly contract: AbstractContract) {}
+  public async propose(proposal: Proposal, extensions: Array<Window>, overrides?: Overrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"propose\", [proposal, extensions], overrides);
+  }
+  public async window(id: bigint | number | string): Promise<WindowOutput> {
+    return await this.contract.read<WindowOutput>(\"window\", id);
+  }
+  public async tally(): Promise<{ \"yes\": bigint, \"no\": bigint }> {
+    return await this.contract.read<{ \"yes\": bigint, \"no\": bigint }>(\"tally\");
+  }
+  public async leader(): Promise<[string, bigint]> {
+    return await this.contract.read<[string, bigint]>(\"leader\");
+  }
+}
+",
+        out
+    );
+}
+
+#[test]
+fn emit_vault_bindings() {
+    let contract = read_artifact(include_str!("fixtures/Vault.json"), None)
+        .unwrap()
+        .remove(0);
+    let out = CodeEmitter::default()
+        .emit(&contract, "./AbstractContract")
+        .unwrap();
+    assert_eq!(
+        "
+import AbstractContract, { ContractTransaction, AbstractDeployer, Overrides, PayableOverrides } from \"./AbstractContract\";
+export class InsufficientBalance extends Error {
+  public readonly name = \"InsufficientBalance\" as const;
+  constructor(public readonly available: bigint, public readonly required: bigint) {
+    super(\"InsufficientBalance\");
+  }
+}
+export class Unauthorized extends Error {
+  public readonly name = \"Unauthorized\" as const;
+  constructor() {
+    super(\"Unauthorized\");
+  }
+}
+export type VaultError = InsufficientBalance | Unauthorized;
+export function decodeVaultError(contract: AbstractContract, data: string): VaultError | undefined {
+  const error = contract.decodeError(data);
+  if (error === undefined) {
+    return undefined;
+  }
+  switch (error.name) {
+    case \"InsufficientBalance\":
+      return new InsufficientBalance(error.args[0], error.args[1]);
+    case \"Unauthorized\":
+      return new Unauthorized();
+  }
+  return undefined;
+}
+export default class Vault {
+  constructor(private readonly contract: AbstractContract) {}
+  public async deposit(overrides?: PayableOverrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"deposit\", [], overrides);
+  }
+  public async depositFor(beneficiary: string, overrides?: PayableOverrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"depositFor\", [beneficiary], overrides);
+  }
+  public async balanceOf(account: string): Promise<bigint> {
+    return await this.contract.read<bigint>(\"balanceOf\", account);
+  }
+  public async sendEther(value: bigint | number | string, overrides?: Overrides): Promise<ContractTransaction> {
+    return await this.contract.sendRaw(\"0x\", { ...overrides, value });
+  }
+  public async fallback(data: string, overrides?: Overrides): Promise<ContractTransaction> {
+    return await this.contract.sendRaw(data, overrides);
+  }
+}
+export class VaultFactory {
+  public static readonly bytecode: string = \"0x608060405234801561001057600080fd5b50\";
+  constructor(private readonly deployer: AbstractDeployer) {}
+  public async deploy(owner: string, feeBasisPoints: number, overrides?: Overrides): Promise<Vault> {
+    return new Vault(await this.deployer.deploy(\"Vault\", VaultFactory.bytecode, [owner, feeBasisPoints], overrides));
+  }
+}
+",
+        out
+    );
+}
+
+#[test]
+fn emit_event_bindings() {
+    let contract = read_artifact(include_str!("fixtures/Token.json"), None)
+        .unwrap()
+        .remove(0);
+    let out = CodeEmitter::default()
+        .emit(&contract, "./AbstractContract")
+        .unwrap();
+    assert_eq!(
+        "
+import AbstractContract, { ContractTransaction, Overrides } from \"./AbstractContract\";
+export interface TransferEvent {
+  from: string;
+  to: string;
+  value: bigint;
+}
+export interface PausedEvent {
+  reason: string;
+}
+export default class Token {
+  constructor(private readonly contract: AbstractContract) {}
+  public async transfer(to: string, amount: bigint | number | string, overrides?: Overrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"transfer\", [to, amount], overrides);
+  }
+  public onTransfer(listener: (event: TransferEvent) => void): () => void {
+    return this.contract.on<TransferEvent>(\"Transfer\", listener);
+  }
+  public async queryTransfer(filter: Partial<{ \"from\": string, \"to\": string }>, fromBlock?: number | string, toBlock?: number | string): Promise<Array<TransferEvent>> {
+    return await this.contract.query<TransferEvent>(\"Transfer\", [filter.from ?? null, filter.to ?? null], fromBlock, toBlock);
+  }
+  public onPaused(listener: (event: PausedEvent) => void): () => void {
+    return this.contract.on<PausedEvent>(\"Paused\", listener);
+  }
+  public async queryPaused(filter: Partial<{}>, fromBlock?: number | string, toBlock?: number | string): Promise<Array<PausedEvent>> {
+    return await this.contract.query<PausedEvent>(\"Paused\", [], fromBlock, toBlock);
+  }
+}
+",
+        out
+    );
+}
+
+#[test]
+fn emit_overloaded_functions() {
+    let contract = read_artifact(include_str!("fixtures/Nft.json"), None)
+        .unwrap()
+        .remove(0);
+    let out = CodeEmitter::default()
+        .emit(&contract, "./AbstractContract")
+        .unwrap();
+    assert_eq!(
+        "
+import AbstractContract, { ContractTransaction, Overrides, PayableOverrides } from \"./AbstractContract\";
+export default class Nft {
+  constructor(private readonly contract: AbstractContract) {}
+  public safeTransferFrom(from: string, to: string, tokenId: bigint | number | string): Promise<ContractTransaction>;
+  public safeTransferFrom(from: string, to: string, tokenId: bigint | number | string, data: string, overrides?: Overrides): Promise<ContractTransaction>;
+  public async safeTransferFrom(...args: Array<any>): Promise<any> {
+    switch (args.length) {
+      case 3:
+        return await this[\"safeTransferFrom(address,address,uint256)\"](args[0], args[1], args[2]);
+      case 4:
+        return await this[\"safeTransferFrom(address,address,uint256,bytes)\"](args[0], args[1], args[2], args[3]);
+      case 5:
+        return await this[\"safeTransferFrom(address,address,uint256,bytes)\"](args[0], args[1], args[2], args[3], args[4]);
+    }
+    throw new Error(\"No overload of safeTransferFrom takes that many arguments\");
+  }
+  public async \"safeTransferFrom(address,address,uint256)\"(from: string, to: string, tokenId: bigint | number | string, overrides?: Overrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"safeTransferFrom(address,address,uint256)\", [from, to, tokenId], overrides);
+  }
+  public async \"safeTransferFrom(address,address,uint256,bytes)\"(from: string, to: string, tokenId: bigint | number | string, data: string, overrides?: Overrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"safeTransferFrom(address,address,uint256,bytes)\", [from, to, tokenId, data], overrides);
+  }
+  public async \"mint(address)\"(to: string, overrides?: PayableOverrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"mint(address)\", [to], overrides);
+  }
+  public async \"mint(uint256)\"(amount: bigint | number | string, overrides?: PayableOverrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"mint(uint256)\", [amount], overrides);
+  }
+}
+",
+        out
+    );
+}
+
+#[test]
+fn emit_wide_integers_as_class() {
+    let contract = read_artifact(include_str!("fixtures/Token.json"), None)
+        .unwrap()
+        .remove(0);
+    let emitter = CodeEmitter {
+        numeric: "BigNumber:ethers".parse().unwrap(),
+        ..CodeEmitter::default()
+    };
+    let out = emitter.emit(&contract, "./AbstractContract").unwrap();
+    assert_eq!(
+        "
+import AbstractContract, { ContractTransaction, Overrides } from \"./AbstractContract\";
+import { BigNumber } from \"ethers\";
+export interface TransferEvent {
+  from: string;
+  to: string;
+  value: BigNumber;
+}
+export interface PausedEvent {
+  reason: string;
+}
+export default class Token {
+  constructor(private readonly contract: AbstractContract) {}
+  public async transfer(to: string, amount: BigNumber | bigint | number | string, overrides?: Overrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"transfer\", [to, amount], overrides);
+  }
+  public onTransfer(listener: (event: TransferEvent) => void): () => void {
+    return this.contract.on<TransferEvent>(\"Transfer\", listener);
+  }
+  public async queryTransfer(filter: Partial<{ \"from\": string, \"to\": string }>, fromBlock?: number | string, toBlock?: number | string): Promise<Array<TransferEvent>> {
+    return await this.contract.query<TransferEvent>(\"Transfer\", [filter.from ?? null, filter.to ?? null], fromBlock, toBlock);
+  }
+  public onPaused(listener: (event: PausedEvent) => void): () => void {
+    return this.contract.on<PausedEvent>(\"Paused\", listener);
+  }
+  public async queryPaused(filter: Partial<{}>, fromBlock?: number | string, toBlock?: number | string): Promise<Array<PausedEvent>> {
+    return await this.contract.query<PausedEvent>(\"Paused\", [], fromBlock, toBlock);
+  }
+}
+",
+        out
+    );
+}
+
+#[test]
+fn parse_numeric_mappings() {
+    assert_eq!("bigint".parse(), Ok(NumericMapping::BigInt));
+    assert_eq!("BigNumberish".parse(), Ok(NumericMapping::BigNumberish));
+    assert_eq!(
+        "Decimal:decimal.js".parse(),
+        Ok(NumericMapping::Class {
+            name: "Decimal".into(),
+            module: "decimal.js".into()
+        })
+    );
+    assert!("number".parse::<NumericMapping>().is_err());
+}
+
+#[test]
+fn emit_contract_abstraction() {
+    assert_eq!(
+        "
+export interface ContractTransaction {
+  hash: string;
+  wait(confirmations?: number): Promise<unknown>;
+}
+export interface Overrides {
+  gasLimit?: bigint | number | string;
+  gasPrice?: bigint | number | string;
+  maxFeePerGas?: bigint | number | string;
+  maxPriorityFeePerGas?: bigint | number | string;
+  nonce?: number;
+}
+export interface PayableOverrides extends Overrides {
+  value?: bigint | number | string;
+}
+export interface DecodedError {
+  name: string;
+  args: Array<any>;
+}
+export interface AbstractDeployer {
+  deploy(name: string, bytecode: string, args: Array<any>, overrides?: PayableOverrides): Promise<AbstractContract>;
+}
+export default interface AbstractContract {
+  read<T>(target: string, ...args: Array<any>): Promise<T>;
+  send(target: string, args: Array<any>, overrides?: PayableOverrides): Promise<ContractTransaction>;
+  sendRaw(data: string, overrides?: PayableOverrides): Promise<ContractTransaction>;
+  on<T>(event: string, listener: (event: T) => void): () => void;
+  query<T>(event: string, filter: Array<any>, fromBlock?: number | string, toBlock?: number | string): Promise<Array<T>>;
+  decodeError(data: string): DecodedError | undefined;
+}
+",
+        CodeEmitter::default().emit_contract_abstraction()
+    );
+}
+
+#[test]
+fn emit_ethers_v6_implementations() {
+    let emitter = CodeEmitter {
+        target: Target::EthersV6,
+        ..CodeEmitter::default()
+    };
+    assert_eq!(
+        "
+import { Contract, ContractFactory, InterfaceAbi, Signer } from \"ethers\";
+export interface ContractTransaction {
+  hash: string;
+  wait(confirmations?: number): Promise<unknown>;
+}
+export interface Overrides {
+  gasLimit?: bigint | number | string;
+  gasPrice?: bigint | number | string;
+  maxFeePerGas?: bigint | number | string;
+  maxPriorityFeePerGas?: bigint | number | string;
+  nonce?: number;
+}
+export interface PayableOverrides extends Overrides {
+  value?: bigint | number | string;
+}
+export interface DecodedError {
+  name: string;
+  args: Array<any>;
+}
+export interface AbstractDeployer {
+  deploy(name: string, bytecode: string, args: Array<any>, overrides?: PayableOverrides): Promise<AbstractContract>;
+}
+export default interface AbstractContract {
+  read<T>(target: string, ...args: Array<any>): Promise<T>;
+  send(target: string, args: Array<any>, overrides?: PayableOverrides): Promise<ContractTransaction>;
+  sendRaw(data: string, overrides?: PayableOverrides): Promise<ContractTransaction>;
+  on<T>(event: string, listener: (event: T) => void): () => void;
+  query<T>(event: string, filter: Array<any>, fromBlock?: number | string, toBlock?: number | string): Promise<Array<T>>;
+  decodeError(data: string): DecodedError | undefined;
+}
+export class EthersContract implements AbstractContract {
+  constructor(public readonly contract: Contract) {}
+  public async read<T>(target: string, ...args: Array<any>): Promise<T> {
+    return await this.contract.getFunction(target).staticCall(...args);
+  }
+  public async send(target: string, args: Array<any>, overrides?: PayableOverrides): Promise<ContractTransaction> {
+    return await this.contract.getFunction(target).send(...args, overrides ?? {});
+  }
+  public async sendRaw(data: string, overrides?: PayableOverrides): Promise<ContractTransaction> {
+    const signer = this.contract.runner as Signer;
+    return await signer.sendTransaction({ ...overrides, to: await this.contract.getAddress(), data });
+  }
+  public on<T>(event: string, listener: (event: T) => void): () => void {
+    const handler = (...args: Array<any>) => listener(args[args.length - 1].args.toObject());
+    void this.contract.on(event, handler);
+    return () => void this.contract.off(event, handler);
+  }
+  public async query<T>(event: string, filter: Array<any>, fromBlock?: number | string, toBlock?: number | string): Promise<Array<T>> {
+    const events = await this.contract.queryFilter(this.contract.filters[event](...filter), fromBlock, toBlock);
+    return events.map((event) => (\"args\" in event ? event.args.toObject() : {}) as T);
+  }
+  public decodeError(data: string): DecodedError | undefined {
+    const error = this.contract.interface.parseError(data);
+    return error === null ? undefined : { name: error.name, args: [...error.args] };
+  }
+}
+export class EthersDeployer implements AbstractDeployer {
+  constructor(private readonly signer: Signer, private readonly abis: Record<string, InterfaceAbi>) {}
+  public async deploy(name: string, bytecode: string, args: Array<any>, overrides?: PayableOverrides): Promise<AbstractContract> {
+    const factory = new ContractFactory(this.abis[name], bytecode, this.signer);
+    const contract = await factory.deploy(...args, overrides ?? {});
+    await contract.waitForDeployment();
+    return new EthersContract(new Contract(await contract.getAddress(), this.abis[name], this.signer));
+  }
+}
+",
+        emitter.emit_contract_abstraction()
+    );
+}
+
+#[test]
+fn emit_bundles_sharing_declarations() {
+    let vault = read_artifact(include_str!("fixtures/Vault.json"), None)
+        .unwrap()
+        .remove(0);
+    let gate = read_artifact(
+        r#"["function open(uint256 amount)", "error Unauthorized()", "error InsufficientBalance(uint256 available)"]"#,
+        Some("Gate"),
+    )
+    .unwrap()
+    .remove(0);
+    let out = CodeEmitter::default()
+        .emit_bundle(&[&vault, &gate])
+        .unwrap();
+    assert_eq!(
+        "
+export interface ContractTransaction {
+  hash: string;
+  wait(confirmations?: number): Promise<unknown>;
+}
+export interface Overrides {
+  gasLimit?: bigint | number | string;
+  gasPrice?: bigint | number | string;
+  maxFeePerGas?: bigint | number | string;
+  maxPriorityFeePerGas?: bigint | number | string;
+  nonce?: number;
+}
+export interface PayableOverrides extends Overrides {
+  value?: bigint | number | string;
+}
+export interface DecodedError {
+  name: string;
+  args: Array<any>;
+}
+export interface AbstractDeployer {
+  deploy(name: string, bytecode: string, args: Array<any>, overrides?: PayableOverrides): Promise<AbstractContract>;
+}
+export interface AbstractContract {
+  read<T>(target: string, ...args: Array<any>): Promise<T>;
+  send(target: string, args: Array<any>, overrides?: PayableOverrides): Promise<ContractTransaction>;
+  sendRaw(data: string, overrides?: PayableOverrides): Promise<ContractTransaction>;
+  on<T>(event: string, listener: (event: T) => void): () => void;
+  query<T>(event: string, filter: Array<any>, fromBlock?: number | string, toBlock?: number | string): Promise<Array<T>>;
+  decodeError(data: string): DecodedError | undefined;
+}
+export class InsufficientBalance extends Error {
+  public readonly name = \"InsufficientBalance\" as const;
+  constructor(public readonly available: bigint, public readonly required: bigint) {
+    super(\"InsufficientBalance\");
+  }
+}
+export class Unauthorized extends Error {
+  public readonly name = \"Unauthorized\" as const;
+  constructor() {
+    super(\"Unauthorized\");
+  }
+}
+export type VaultError = InsufficientBalance | Unauthorized;
+export function decodeVaultError(contract: AbstractContract, data: string): VaultError | undefined {
+  const error = contract.decodeError(data);
+  if (error === undefined) {
+    return undefined;
+  }
+  switch (error.name) {
+    case \"InsufficientBalance\":
+      return new InsufficientBalance(error.args[0], error.args[1]);
+    case \"Unauthorized\":
+      return new Unauthorized();
+  }
+  return undefined;
+}
+export class Vault {
+  constructor(private readonly contract: AbstractContract) {}
+  public async deposit(overrides?: PayableOverrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"deposit\", [], overrides);
+  }
+  public async depositFor(beneficiary: string, overrides?: PayableOverrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"depositFor\", [beneficiary], overrides);
+  }
+  public async balanceOf(account: string): Promise<bigint> {
+    return await this.contract.read<bigint>(\"balanceOf\", account);
+  }
+  public async sendEther(value: bigint | number | string, overrides?: Overrides): Promise<ContractTransaction> {
+    return await this.contract.sendRaw(\"0x\", { ...overrides, value });
+  }
+  public async fallback(data: string, overrides?: Overrides): Promise<ContractTransaction> {
+    return await this.contract.sendRaw(data, overrides);
+  }
+}
+export class VaultFactory {
+  public static readonly bytecode: string = \"0x608060405234801561001057600080fd5b50\";
+  constructor(private readonly deployer: AbstractDeployer) {}
+  public async deploy(owner: string, feeBasisPoints: number, overrides?: Overrides): Promise<Vault> {
+    return new Vault(await this.deployer.deploy(\"Vault\", VaultFactory.bytecode, [owner, feeBasisPoints], overrides));
+  }
+}
+export class GateInsufficientBalance extends Error {
+  public readonly name = \"InsufficientBalance\" as const;
+  constructor(public readonly available: bigint) {
+    super(\"InsufficientBalance\");
+  }
+}
+export type GateError = Unauthorized | GateInsufficientBalance;
+export function decodeGateError(contract: AbstractContract, data: string): GateError | undefined {
+  const error = contract.decodeError(data);
+  if (error === undefined) {
+    return undefined;
+  }
+  switch (error.name) {
+    case \"Unauthorized\":
+      return new Unauthorized();
+    case \"InsufficientBalance\":
+      return new GateInsufficientBalance(error.args[0]);
+  }
+  return undefined;
+}
+export class Gate {
+  constructor(private readonly contract: AbstractContract) {}
+  public async open(amount: bigint | number | string, overrides?: Overrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"open\", [amount], overrides);
+  }
+}
+",
+        out
+    );
+}
+
+#[test]
+fn emit_index_of_modules() {
+    let token = read_artifact(include_str!("fixtures/Token.json"), None)
+        .unwrap()
+        .remove(0);
+    let emitter = CodeEmitter::default();
+    let exports = emitter.exports(&token).unwrap();
+    // Later modules leave the names exported before to the first one.
+    let out = emitter.emit_index(&[
+        ("./Token".into(), "Token", &exports),
+        ("./legacy/Token".into(), "Token", &exports),
+        ("./Nft".into(), "Nft", &["TransferEvent".into()]),
+    ]);
+    assert_eq!(
+        "
+export { default as AbstractContract, ContractTransaction, Overrides, PayableOverrides, DecodedError, AbstractDeployer } from \"./AbstractContract\";
+export { default as Token, TransferEvent, PausedEvent } from \"./Token\";
+export { default as Nft } from \"./Nft\";
+",
+        out
+    );
+}
+
+#[test]
+fn emit_bare_abis_like_artifacts() {
+    let artifact = read_artifact(include_str!("fixtures/Token.json"), None)
+        .unwrap()
+        .remove(0);
+    let abi = read_artifact(include_str!("fixtures/Token.abi.json"), Some("Token"))
+        .unwrap()
+        .remove(0);
+    let emitter = CodeEmitter::default();
+    assert_eq!(
+        emitter.emit(&artifact, "./AbstractContract").unwrap(),
+        emitter.emit(&abi, "./AbstractContract").unwrap()
+    );
+}
+
+#[test]
+fn emit_overloaded_events() {
+    let contract = read_artifact(include_str!("fixtures/Ledger.abi.json"), Some("Ledger"))
+        .unwrap()
+        .remove(0);
+    let out = CodeEmitter::default()
+        .emit(&contract, "./AbstractContract")
+        .unwrap();
+    assert_eq!(
+        "
+import AbstractContract from \"./AbstractContract\";
+export interface E_uint256Event {
+  a: bigint;
+}
+export interface E_uint256_uint256Event {
+  a: bigint;
+  b: bigint;
+}
+export default class Ledger {
+  constructor(private readonly contract: AbstractContract) {}
+  public \"onE(uint256)\"(listener: (event: E_uint256Event) => void): () => void {
+    return this.contract.on<E_uint256Event>(\"E(uint256)\", listener);
+  }
+  public async \"queryE(uint256)\"(filter: Partial<{ \"a\": bigint | number | string }>, fromBlock?: number | string, toBlock?: number | string): Promise<Array<E_uint256Event>> {
+    return await this.contract.query<E_uint256Event>(\"E(uint256)\", [filter.a ?? null], fromBlock, toBlock);
+  }
+  public \"onE(uint256,uint256)\"(listener: (event: E_uint256_uint256Event) => void): () => void {
+    return this.contract.on<E_uint256_uint256Event>(\"E(uint256,uint256)\", listener);
+  }
+  public async \"queryE(uint256,uint256)\"(filter: Partial<{ \"a\": bigint | number | string }>, fromBlock?: number | string, toBlock?: number | string): Promise<Array<E_uint256_uint256Event>> {
+    return await this.contract.query<E_uint256_uint256Event>(\"E(uint256,uint256)\", [filter.a ?? null], fromBlock, toBlock);
+  }
+}
+",
+        out
+    );
+}
+
+#[test]
+fn emit_factories_linking_libraries() {
+    let contract = read_artifact(include_str!("fixtures/hardhat/Counter.json"), None)
+        .unwrap()
+        .remove(0);
+    let out = CodeEmitter::default()
+        .emit(&contract, "./AbstractContract")
+        .unwrap();
+    assert_eq!(
+        "
+import AbstractContract, { ContractTransaction, AbstractDeployer, Overrides } from \"./AbstractContract\";
+export interface IncrementedEvent {
+  value: bigint;
+}
+export default class Counter {
+  constructor(private readonly contract: AbstractContract) {}
+  public async count(): Promise<bigint> {
+    return await this.contract.read<bigint>(\"count\");
+  }
+  public async increment(overrides?: Overrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"increment\", [], overrides);
+  }
+  public onIncremented(listener: (event: IncrementedEvent) => void): () => void {
+    return this.contract.on<IncrementedEvent>(\"Incremented\", listener);
+  }
+  public async queryIncremented(filter: Partial<{}>, fromBlock?: number | string, toBlock?: number | string): Promise<Array<IncrementedEvent>> {
+    return await this.contract.query<IncrementedEvent>(\"Incremented\", [], fromBlock, toBlock);
+  }
+}
+export class CounterFactory {
+  public static readonly bytecode: string = \"0x6080604052348015600f57600080fd5b5060006073__$8f2e1ba5b3ca3b2b0b0a8e4d6fd4f1c7b5$__63\";
+  constructor(private readonly deployer: AbstractDeployer, private readonly libraries: { \"contracts/Math.sol:Math\": string }) {}
+  public async deploy(overrides?: Overrides): Promise<Counter> {
+    return new Counter(await this.deployer.deploy(\"Counter\", this.link(), [], overrides));
+  }
+  private link(): string {
+    let bytecode = CounterFactory.bytecode;
+    bytecode = bytecode.slice(0, 44) + this.libraries[\"contracts/Math.sol:Math\"].slice(2) + bytecode.slice(84);
+    return bytecode;
+  }
+}
+",
+        out
+    );
+}
+
+#[test]
+fn emit_narrow_integers_as_bigint_for_ethers_v6() {
+    let contract = read_artifact(include_str!("fixtures/Dice.abi.json"), Some("Dice"))
+        .unwrap()
+        .remove(0);
+    let emitter = CodeEmitter {
+        target: Target::EthersV6,
+        ..CodeEmitter::default()
+    };
+    let out = emitter.emit(&contract, "./AbstractContract").unwrap();
+    assert_eq!(
+        "
+import AbstractContract, { ContractTransaction, Overrides } from \"./AbstractContract\";
+export interface RolledEvent {
+  face: bigint;
+}
+export class BadFace extends Error {
+  public readonly name = \"BadFace\" as const;
+  constructor(public readonly face: bigint) {
+    super(\"BadFace\");
+  }
+}
+export type DiceError = BadFace;
+export function decodeDiceError(contract: AbstractContract, data: string): DiceError | undefined {
+  const error = contract.decodeError(data);
+  if (error === undefined) {
+    return undefined;
+  }
+  switch (error.name) {
+    case \"BadFace\":
+      return new BadFace(error.args[0]);
+  }
+  return undefined;
+}
+export default class Dice {
+  constructor(private readonly contract: AbstractContract) {}
+  public async faces(): Promise<bigint> {
+    return await this.contract.read<bigint>(\"faces\");
+  }
+  public async cheat(face: number, overrides?: Overrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"cheat\", [face], overrides);
+  }
+  public onRolled(listener: (event: RolledEvent) => void): () => void {
+    return this.contract.on<RolledEvent>(\"Rolled\", listener);
+  }
+  public async queryRolled(filter: Partial<{ \"face\": number }>, fromBlock?: number | string, toBlock?: number | string): Promise<Array<RolledEvent>> {
+    return await this.contract.query<RolledEvent>(\"Rolled\", [filter.face ?? null], fromBlock, toBlock);
+  }
+}
+",
+        out
+    );
+}
+
+#[test]
+fn rename_params_clashing_with_overrides() {
+    let contract = read_artifact(include_str!("fixtures/Relay.abi.json"), Some("Relay"))
+        .unwrap()
+        .remove(0);
+    let out = CodeEmitter::default()
+        .emit(&contract, "./AbstractContract")
+        .unwrap();
+    assert_eq!(
+        "
+import AbstractContract, { ContractTransaction, Overrides } from \"./AbstractContract\";
+export default class Relay {
+  constructor(private readonly contract: AbstractContract) {}
+  public async relay(overrides__: bigint | number | string, overrides_: string, overrides?: Overrides): Promise<ContractTransaction> {
+    return await this.contract.send(\"relay\", [overrides__, overrides_], overrides);
+  }
+}
+",
+        out
+    );
+}
+
+#[test]
+fn rename_error_params_clashing_with_error_fields() {
+    let contract = read_artifact(include_str!("fixtures/Guard.abi.json"), Some("Guard"))
+        .unwrap()
+        .remove(0);
+    let out = CodeEmitter::default()
+        .emit(&contract, "./AbstractContract")
+        .unwrap();
+    assert_eq!(
+        "
+import AbstractContract from \"./AbstractContract\";
+export class Denied extends Error {
+  public readonly name = \"Denied\" as const;
+  constructor(public readonly name_: string, public readonly message_: bigint, public readonly stack_: boolean) {
+    super(\"Denied\");
+  }
+}
+export type GuardError = Denied;
+export function decodeGuardError(contract: AbstractContract, data: string): GuardError | undefined {
+  const error = contract.decodeError(data);
+  if (error === undefined) {
+    return undefined;
+  }
+  switch (error.name) {
+    case \"Denied\":
+      return new Denied(error.args[0], error.args[1], error.args[2]);
+  }
+  return undefined;
+}
+export default class Guard {
+  constructor(private readonly contract: AbstractContract) {}
+}
+",
+        out
+    );
+}
+
+#[test]
+fn leave_ambiguous_overloads_to_their_aliases() {
+    let contract = read_artifact(include_str!("fixtures/Oracle.abi.json"), Some("Oracle"))
+        .unwrap()
+        .remove(0);
+    let out = CodeEmitter::default()
+        .emit(&contract, "./AbstractContract")
+        .unwrap();
+    assert_eq!(
+        "
+import AbstractContract from \"./AbstractContract\";
+export default class Oracle {
+  constructor(private readonly contract: AbstractContract) {}
+  public f(a: bigint | number | string, b: bigint | number | string): Promise<bigint>;
+  public async f(...args: Array<any>): Promise<any> {
+    switch (args.length) {
+      case 1:
+        throw new Error(\"Ambiguous call to f, use this[\\\"f(uint256)\\\"] or this[\\\"f(address)\\\"] instead\");
+      case 2:
+        return await this[\"f(uint256,uint256)\"](args[0], args[1]);
+    }
+    throw new Error(\"No overload of f takes that many arguments\");
+  }
+  public async \"f(uint256)\"(a: bigint | number | string): Promise<bigint> {
+    return await this.contract.read<bigint>(\"f(uint256)\", a);
+  }
+  public async \"f(address)\"(a: string): Promise<boolean> {
+    return await this.contract.read<boolean>(\"f(address)\", a);
+  }
+  public async \"f(uint256,uint256)\"(a: bigint | number | string, b: bigint | number | string): Promise<bigint> {
+    return await this.contract.read<bigint>(\"f(uint256,uint256)\", a, b);
+  }
+}
+",
+        out
+    );
+}
@@ -0,0 +1,71 @@
+use crate::{
+    code_emitter::CodeEmitter,
+    error::{Error, Location},
+    parser::read_artifact,
+};
+
+const MALFORMED: &str = r#"{
+  "contractName": "Broken",
+  "abi": [
+    { "inputs": [], "name": "ok", "outputs": [], "stateMutability": "view", "type": "function" },
+    {
+      "inputs": [{ "internalType": "uint7", "name": "x", "type": "uint256" }],
+      "name": "broken",
+      "outputs": [],
+      "stateMutability": "view",
+      "type": "function"
+    }
+  ]
+}"#;
+
+#[test]
+fn locate_parse_errors() {
+    let Error::Parse {
+        file,
+        location,
+        message,
+    } = read_artifact(MALFORMED, None).unwrap_err()
+    else {
+        panic!("expected a parse error");
+    };
+    assert_eq!(file, None);
+    assert_eq!(
+        location,
+        Some(Location {
+            line: 6,
+            column: 42,
+            path: "abi[1].inputs[0].internalType".into(),
+        })
+    );
+    assert_eq!(message, "invalid integer width in `uint7`");
+}
+
+#[test]
+fn display_parse_errors_like_compilers() {
+    let err = read_artifact(MALFORMED, None)
+        .unwrap_err()
+        .in_file("build/Broken.json");
+    assert_eq!(
+        err.to_string(),
+        "build/Broken.json:6:42: invalid integer width in `uint7` (at abi[1].inputs[0].internalType)"
+    );
+    let err = read_artifact("[]", None).unwrap_err().in_file("Bare.json");
+    assert_eq!(
+        err.to_string(),
+        "Bare.json: a bare ABI names no contract, and no name was given"
+    );
+    let err = read_artifact("{ \"abi\": [", None).unwrap_err();
+    assert_eq!(err.to_string(), "1:10: EOF while parsing a list (at abi)");
+}
+
+#[test]
+fn reject_contract_names_that_are_not_identifiers() {
+    let contract = read_artifact("[]", Some("erc20-abi")).unwrap().remove(0);
+    let err = CodeEmitter::default()
+        .emit(&contract, "./AbstractContract")
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "erc20-abi: the contract name isn't a valid class name"
+    );
+}
@@ -0,0 +1 @@
+export const handWritten = true;
@@ -0,0 +1,3 @@
+// Generated by spider-jockey, do not edit.
+
+export default class Kept {}
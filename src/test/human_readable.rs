@@ -0,0 +1,79 @@
+use crate::{
+    human_readable::{format_abi, parse_abi},
+    parser::{read_artifact, AbiEntry, DataType},
+};
+
+#[test]
+fn parse_function_signatures() {
+    let abi = parse_abi(&[
+        "function balanceOf(address owner) view returns (uint256)",
+        "function transfer(address to, uint amount) external returns (bool success);",
+        "function deposit() public payable",
+        "function swap(tuple(address token, uint256[2] amounts)[] calldata orders, (bytes32, bool) flags)",
+    ])
+    .unwrap();
+    assert_eq!(
+        format_abi(&abi),
+        [
+            "function balanceOf(address owner) view returns (uint256)",
+            "function transfer(address to, uint256 amount) returns (bool success)",
+            "function deposit() payable",
+            "function swap(tuple(address token, uint256[2] amounts)[] orders, tuple(bytes32, bool) flags)",
+        ]
+    );
+    let AbiEntry::Function { inputs, .. } = &abi[3] else {
+        panic!("expected a function");
+    };
+    assert_eq!(
+        inputs[0].io_type,
+        DataType::Array(Box::new(DataType::Tuple), None)
+    );
+    assert_eq!(inputs[0].components.len(), 2);
+}
+
+#[test]
+fn parse_other_entries() {
+    let signatures = [
+        "constructor(address owner, uint16 feeBasisPoints) payable",
+        "event Transfer(address indexed from, address indexed to, uint256 value)",
+        "event Log(bytes data) anonymous",
+        "error InsufficientBalance(uint256 available, uint256 required)",
+        "receive() external payable",
+        "fallback() external",
+    ];
+    assert_eq!(format_abi(&parse_abi(&signatures).unwrap()), signatures);
+}
+
+#[test]
+fn reject_malformed_signatures() {
+    for signature in [
+        "function balanceOf(address owner",
+        "function balanceOf(uint7 owner)",
+        "modifier onlyOwner()",
+        "function f() view returns (uint256) extra",
+        "function f(address owner) % view",
+    ] {
+        let error = parse_abi(&[signature]).unwrap_err();
+        assert!(error.contains(signature), "{}", error);
+    }
+}
+
+#[test]
+fn format_artifacts_round_trip() {
+    let contract = read_artifact(include_str!("fixtures/Dao.json"), None)
+        .unwrap()
+        .remove(0);
+    let signatures = format_abi(&contract.abi);
+    assert_eq!(format_abi(&parse_abi(&signatures).unwrap()), signatures);
+}
+
+#[test]
+fn read_human_readable_artifacts() {
+    let abi = r#"[
+        "function transfer(address to, uint256 amount) returns (bool)",
+        "event Transfer(address indexed from, address indexed to, uint256 value)"
+    ]"#;
+    let contract = read_artifact(abi, Some("Token")).unwrap().remove(0);
+    assert_eq!(contract.name, "Token");
+    assert_eq!(contract.abi.len(), 2);
+}
@@ -0,0 +1,125 @@
+use std::{
+    env,
+    fs::{self, File},
+    path::Path,
+    process,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    cli::{Options, Verbosity},
+    compile,
+    config::Filter,
+    poll, Sink,
+};
+
+/// Compares against the disk, so that nothing gets written.
+fn check() -> Sink {
+    Sink::Check {
+        expected: vec![],
+        diffs: vec![],
+    }
+}
+
+#[test]
+fn refuse_contracts_sharing_a_file() {
+    let options = Options {
+        out_dir: "generated".into(),
+        ..Default::default()
+    };
+    let input = "src/test/fixtures/solc/clashing.json";
+    let err = match compile(&options, input, None, &mut check()) {
+        Err(err) => err.to_string(),
+        Ok(_) => panic!("expected an error"),
+    };
+    assert_eq!(
+        "b/IERC20.sol:IERC20: it would overwrite the file of `a/IERC20.sol:IERC20`, pick one of them with `--contract`",
+        err
+    );
+    let options = Options {
+        contracts: Filter {
+            include: vec!["b/*".into()],
+            exclude: vec![],
+        },
+        ..options
+    };
+    let modules = compile(&options, input, None, &mut check()).unwrap();
+    assert_eq!(
+        vec![Path::new("generated/IERC20.ts")],
+        modules
+            .iter()
+            .map(|module| module.file.as_path())
+            .collect::<Vec<_>>()
+    );
+}
+
+/// Writes an input, dating it after its previous versions.
+fn touch(path: &Path, contents: &str, age: u64) {
+    fs::write(path, contents).unwrap();
+    File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(age))
+        .unwrap();
+}
+
+#[test]
+fn regenerate_the_inputs_that_change() {
+    let dir = env::temp_dir().join(format!("spider-jockey-watch-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("in")).unwrap();
+    let token = include_str!("fixtures/Token.json");
+    touch(&dir.join("in/Token.json"), token, 1);
+    let options = Options {
+        patterns: vec![(dir.join("in/*.json").display().to_string(), None)],
+        out_dir: dir.join("out"),
+        verbosity: Verbosity::Quiet,
+        ..Default::default()
+    };
+    let mut inputs = vec![];
+    assert!(poll(&options, &mut inputs));
+    assert!(dir.join("out/Token.ts").is_file());
+    // Unchanged inputs aren't regenerated.
+    fs::remove_file(dir.join("out/Token.ts")).unwrap();
+    assert!(!poll(&options, &mut inputs));
+    assert!(!dir.join("out/Token.ts").exists());
+    touch(&dir.join("in/Token.json"), token, 2);
+    assert!(poll(&options, &mut inputs));
+    assert!(dir.join("out/Token.ts").is_file());
+    // Malformed inputs are reported, and retried once they change again.
+    touch(&dir.join("in/Vault.json"), "{ \"abi\": [", 1);
+    assert!(poll(&options, &mut inputs));
+    assert_eq!(2, inputs.len());
+    assert!(!dir.join("out/Vault.ts").exists());
+    assert!(!poll(&options, &mut inputs));
+    touch(
+        &dir.join("in/Vault.json"),
+        include_str!("fixtures/Vault.json"),
+        2,
+    );
+    assert!(poll(&options, &mut inputs));
+    assert!(dir.join("out/Vault.ts").is_file());
+    // Inputs no longer matching are dropped.
+    fs::remove_file(dir.join("in/Token.json")).unwrap();
+    assert!(poll(&options, &mut inputs));
+    assert_eq!(1, inputs.len());
+    // Missing literal inputs are kept, and reported once until they are back.
+    let nft = dir.join("Nft.json");
+    touch(&nft, include_str!("fixtures/Nft.json"), 1);
+    let options = Options {
+        patterns: vec![(nft.display().to_string(), None)],
+        ..options
+    };
+    let mut inputs = vec![];
+    assert!(poll(&options, &mut inputs));
+    fs::remove_file(&nft).unwrap();
+    assert!(poll(&options, &mut inputs));
+    assert!(!poll(&options, &mut inputs));
+    assert_eq!(1, inputs.len());
+    fs::remove_file(dir.join("out/Nft.ts")).unwrap();
+    touch(&nft, include_str!("fixtures/Nft.json"), 2);
+    assert!(poll(&options, &mut inputs));
+    assert!(dir.join("out/Nft.ts").is_file());
+    fs::remove_dir_all(&dir).unwrap();
+}
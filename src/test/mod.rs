@@ -1,3 +1,10 @@
+mod check;
+mod cli;
+mod code_emitter;
+mod error;
+mod human_readable;
+mod main;
+mod parser;
 mod ts;
 
 // use crate::parser::Contract;
@@ -0,0 +1,275 @@
+use crate::parser::{
+    read_artifact, signature, AbiEntry, DataType, Format, LinkReference, StateMutability,
+};
+
+#[test]
+fn parse_elementary_types() {
+    let cases = [
+        ("uint8", DataType::UInt(8)),
+        ("uint256", DataType::UInt(256)),
+        ("uint", DataType::UInt(256)),
+        ("int24", DataType::Int(24)),
+        ("address", DataType::Address),
+        ("address payable", DataType::Address),
+        ("bool", DataType::Bool),
+        ("string", DataType::String),
+        ("bytes", DataType::Bytes),
+        ("bytes1", DataType::FixedBytes(1)),
+        ("bytes32", DataType::FixedBytes(32)),
+        ("fixed", DataType::Fixed(128, 18)),
+        ("ufixed64x10", DataType::UFixed(64, 10)),
+        ("function", DataType::Function),
+        ("contract IERC20", DataType::Contract("IERC20".into())),
+        ("enum Dao.Status", DataType::Enum("Dao.Status".into())),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(input.parse::<DataType>(), Ok(expected), "parsing {}", input);
+    }
+}
+
+#[test]
+fn reject_invalid_sizes() {
+    for input in [
+        "uint7",
+        "int264",
+        "bytes0",
+        "bytes33",
+        "fixed128x81",
+        "ufixed8",
+    ] {
+        assert!(input.parse::<DataType>().is_err(), "parsing {}", input);
+    }
+}
+
+#[test]
+fn data_type_round_trips_through_json() {
+    let ty: DataType = serde_json::from_str("\"ufixed128x18\"").unwrap();
+    assert_eq!(ty, DataType::UFixed(128, 18));
+    assert_eq!(serde_json::to_string(&ty).unwrap(), "\"ufixed128x18\"");
+}
+
+#[test]
+fn parse_array_types() {
+    let array = |inner, size| DataType::Array(Box::new(inner), size);
+    let cases = [
+        ("address[]", array(DataType::Address, None)),
+        ("uint256[3]", array(DataType::UInt(256), Some(3))),
+        (
+            "bytes32[][]",
+            array(array(DataType::FixedBytes(32), None), None),
+        ),
+        ("string[2][]", array(array(DataType::String, Some(2)), None)),
+        (
+            "contract IERC20[]",
+            array(DataType::Contract("IERC20".into()), None),
+        ),
+        ("tuple[2]", array(DataType::Tuple, Some(2))),
+        (
+            "struct Dao.Window[]",
+            array(DataType::Struct("Dao.Window".into()), None),
+        ),
+    ];
+    for (input, expected) in cases {
+        let parsed = input.parse::<DataType>();
+        assert_eq!(parsed, Ok(expected), "parsing {}", input);
+        assert_eq!(parsed.unwrap().to_string(), input);
+    }
+}
+
+#[test]
+fn reject_malformed_arrays() {
+    for input in ["uint256]", "uint256[0]", "uint256[x]", "uint7[]"] {
+        assert!(input.parse::<DataType>().is_err(), "parsing {}", input);
+    }
+}
+
+#[test]
+fn tuple_signatures_spell_out_components() {
+    let contract = read_artifact(include_str!("fixtures/Dao.json"), None)
+        .unwrap()
+        .remove(0);
+    let AbiEntry::Function { name, inputs, .. } = &contract.abi[0] else {
+        panic!("expected a function");
+    };
+    assert_eq!(
+        signature(name, inputs),
+        "propose((string,address[],(uint64,uint64)),(uint64,uint64)[])"
+    );
+}
+
+#[test]
+fn read_hardhat_artifacts() {
+    let artifact = include_str!("fixtures/hardhat/Counter.json");
+    assert_eq!(Format::detect(artifact).unwrap(), Format::Hardhat);
+    let contract = read_artifact(artifact, None).unwrap().remove(0);
+    assert_eq!(contract.name, "Counter");
+    assert_eq!(
+        contract.source_name.as_deref(),
+        Some("contracts/Counter.sol")
+    );
+    assert_eq!(
+        contract.deployed_bytecode.as_deref(),
+        Some("0x6080604052348015600f57600080fd5b50")
+    );
+    assert_eq!(
+        contract.link_references["contracts/Math.sol"]["Math"],
+        [LinkReference {
+            start: 21,
+            length: 20
+        }]
+    );
+    assert!(contract.deployed_link_references.is_empty());
+}
+
+#[test]
+fn reject_hardhat_debug_files() {
+    let error = read_artifact(include_str!("fixtures/hardhat/Counter.dbg.json"), None).unwrap_err();
+    assert!(
+        error.to_string().contains("Hardhat debug file"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn detect_truffle_artifacts() {
+    assert_eq!(
+        Format::detect(include_str!("fixtures/Vault.json")).unwrap(),
+        Format::Truffle
+    );
+}
+
+#[test]
+fn read_foundry_artifacts() {
+    let artifact = include_str!("fixtures/foundry/Counter.json");
+    assert_eq!(Format::detect(artifact).unwrap(), Format::Foundry);
+    let contract = read_artifact(artifact, Some("Ignored")).unwrap().remove(0);
+    assert_eq!(contract.name, "Counter");
+    assert_eq!(contract.source_name.as_deref(), Some("src/Counter.sol"));
+    assert_eq!(
+        contract.bytecode.as_deref(),
+        Some("0x6080604052348015600e575f80fd5b50")
+    );
+    assert_eq!(
+        contract.method_identifiers["setNumber(uint256)"],
+        "3fb5c1cb"
+    );
+}
+
+#[test]
+fn name_foundry_artifacts_without_metadata_after_the_file() {
+    let artifact = include_str!("fixtures/foundry/Unnamed.json");
+    let contract = read_artifact(artifact, Some("Unnamed")).unwrap().remove(0);
+    assert_eq!(contract.name, "Unnamed");
+    assert_eq!(contract.source_name, None);
+    assert!(read_artifact(artifact, None).is_err());
+}
+
+#[test]
+fn read_bare_abis() {
+    let abi = include_str!("fixtures/Token.abi.json");
+    assert_eq!(Format::detect(abi).unwrap(), Format::Abi);
+    let contract = read_artifact(abi, Some("Token")).unwrap().remove(0);
+    assert_eq!(contract.name, "Token");
+    assert_eq!(contract.abi.len(), 4);
+    assert_eq!(contract.bytecode, None);
+    assert!(read_artifact(abi, None).is_err());
+}
+
+#[test]
+fn read_every_contract_of_standard_json_outputs() {
+    let output = include_str!("fixtures/solc/output.json");
+    assert_eq!(Format::detect(output).unwrap(), Format::StandardJson);
+    let contracts = read_artifact(output, None).unwrap();
+    let names: Vec<_> = contracts
+        .iter()
+        .map(|contract| (contract.source_name.as_deref(), contract.name.as_str()))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            (Some("contracts/Counter.sol"), "Counter"),
+            (Some("contracts/Counter.sol"), "ICounter"),
+            (Some("contracts/Math.sol"), "Math"),
+        ]
+    );
+    assert_eq!(
+        contracts[0].bytecode.as_deref(),
+        Some("0x6080604052348015600f57600080fd5b50")
+    );
+    assert_eq!(contracts[1].bytecode.as_deref(), Some(""));
+    assert_eq!(contracts[0].method_identifiers["count()"], "06661abd");
+}
+
+#[test]
+fn reject_failed_standard_json_outputs() {
+    let error = read_artifact(include_str!("fixtures/solc/failed.json"), None).unwrap_err();
+    assert!(error.to_string().contains("Expected ';'"), "{}", error);
+}
+
+#[test]
+fn read_vyper_abis() {
+    let contract = read_artifact(
+        include_str!("fixtures/vyper/CurvePool.json"),
+        Some("CurvePool"),
+    )
+    .unwrap()
+    .remove(0);
+    let mutabilities: Vec<_> = contract
+        .abi
+        .iter()
+        .filter_map(|entry| match entry {
+            AbiEntry::Constructor { mutability, .. } => Some(("constructor", mutability)),
+            AbiEntry::Function {
+                name, mutability, ..
+            } => Some((name.as_str(), mutability)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        mutabilities,
+        [
+            ("constructor", &StateMutability::NonPayable),
+            ("get_virtual_price", &StateMutability::View),
+            ("add_liquidity", &StateMutability::Payable),
+            ("exchange", &StateMutability::NonPayable),
+        ]
+    );
+    let AbiEntry::Event { inputs, .. } = &contract.abi[0] else {
+        panic!("expected an event");
+    };
+    assert_eq!(inputs[1].internal_type, DataType::Int(128));
+}
+
+#[test]
+fn read_legacy_abis() {
+    let contract = read_artifact(include_str!("fixtures/legacy/LegacyToken.json"), None)
+        .unwrap()
+        .remove(0);
+    assert_eq!(
+        contract.bytecode.as_deref(),
+        Some("0x6060604052341561000c57fe5b")
+    );
+    let mutabilities: Vec<_> = contract
+        .abi
+        .iter()
+        .filter_map(|entry| match entry {
+            AbiEntry::Constructor { mutability, .. } => Some(("constructor", mutability)),
+            AbiEntry::Fallback { mutability } => Some(("fallback", mutability)),
+            AbiEntry::Function {
+                name, mutability, ..
+            } => Some((name.as_str(), mutability)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        mutabilities,
+        [
+            ("balanceOf", &StateMutability::View),
+            ("transfer", &StateMutability::NonPayable),
+            ("buy", &StateMutability::Payable),
+            ("constructor", &StateMutability::NonPayable),
+            ("fallback", &StateMutability::Payable),
+        ]
+    );
+}
@@ -89,3 +89,25 @@ export default class MyClass {
         out
     );
 }
+
+#[test]
+fn create_imports() {
+    let out = ts::Script::new()
+        .import()
+        .by_default("AbstractContract")
+        .named(&["ContractTransaction"])
+        .from("./AbstractContract")
+        .import_end()
+        .import()
+        .named(&["BigNumberish", "Overrides"])
+        .from("ethers")
+        .import_end()
+        .collect();
+    assert_eq!(
+        "
+import AbstractContract, { ContractTransaction } from \"./AbstractContract\";
+import { BigNumberish, Overrides } from \"ethers\";
+",
+        out
+    );
+}
@@ -1,9 +1,10 @@
-use std::collections::HashMap;
+use std::fmt;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Type {
     Array(Box<Type>),
     Number,
+    BigInt,
     String,
     Boolean,
     Object,
@@ -11,55 +12,72 @@ pub enum Type {
     Unknown,
     Never,
     Partial(Box<Type>),
-    Interface(HashMap<String, Type>),
+    Interface(Vec<(String, Type)>),
     Record(Box<Type>, Box<Type>),
     Tuple(Vec<Type>),
     Null,
+    Undefined,
+    Void,
     Union(Vec<Type>),
     Class(String),
     Promise(Box<Type>),
+    Function(Vec<(String, Type)>, Box<Type>),
 }
-impl Type {
-    pub fn to_string(self) -> String {
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Type::Promise(awaited) => format!("Promise<{}>", awaited.to_string()),
-            Type::Boolean => "boolean".to_owned(),
-            Type::Class(name) => name,
-            Type::Array(t) => format!("Array<{}>", t.to_string()),
-            Type::Number => "number".to_owned(),
-            Type::String => "string".to_owned(),
-            Type::Object => "object".to_owned(),
-            Type::Any => "any".to_owned(),
-            Type::Unknown => "unknown".to_owned(),
-            Type::Never => "never".to_owned(),
-            Type::Partial(t) => format!("Partial<{}>", t.to_string()),
-            Type::Interface(fields) => format!(
+            Type::Promise(awaited) => write!(f, "Promise<{}>", awaited),
+            Type::Boolean => f.write_str("boolean"),
+            Type::Class(name) => f.write_str(name),
+            Type::Array(t) => write!(f, "Array<{}>", t),
+            Type::Number => f.write_str("number"),
+            Type::BigInt => f.write_str("bigint"),
+            Type::String => f.write_str("string"),
+            Type::Object => f.write_str("object"),
+            Type::Any => f.write_str("any"),
+            Type::Unknown => f.write_str("unknown"),
+            Type::Never => f.write_str("never"),
+            Type::Partial(t) => write!(f, "Partial<{}>", t),
+            Type::Interface(fields) if fields.is_empty() => f.write_str("{}"),
+            Type::Interface(fields) => write!(
+                f,
                 "{{ {} }}",
                 fields
                     .iter()
-                    .map(|pair| format!(
-                        "\"{}\": {}",
-                        pair.0.to_string(),
-                        pair.1.clone().to_string()
-                    ))
+                    .map(|pair| format!("\"{}\": {}", pair.0, pair.1))
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
-            Type::Record(k, v) => format!("Record<{}, {}>", k.to_string(), v.to_string()),
-            Type::Tuple(fields) => format!(
+            Type::Record(k, v) => write!(f, "Record<{}, {}>", k, v),
+            Type::Tuple(fields) => write!(
+                f,
                 "[{}]",
                 fields
                     .iter()
-                    .map(|x| x.clone().to_string())
+                    .map(|x| x.to_string())
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
-            Type::Null => "null".to_owned(),
-            Type::Union(fields) => fields
-                .iter()
-                .map(|x| x.clone().to_string())
-                .collect::<Vec<_>>()
-                .join(" | "),
+            Type::Null => f.write_str("null"),
+            Type::Undefined => f.write_str("undefined"),
+            Type::Void => f.write_str("void"),
+            Type::Function(params, returns) => write!(
+                f,
+                "({}) => {}",
+                params
+                    .iter()
+                    .map(|(name, kind)| format!("{}: {}", name, kind))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                returns
+            ),
+            Type::Union(fields) => f.write_str(
+                &fields
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" | "),
+            ),
         }
     }
 }
@@ -70,15 +88,14 @@ pub enum Visibility {
     Private,
     NotSpecified,
 }
-impl Visibility {
-    pub fn to_string(self) -> String {
-        match self {
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
             Visibility::Public => "public",
             Visibility::Protected => "protected",
             Visibility::Private => "private",
             Visibility::NotSpecified => "",
-        }
-        .to_string()
+        })
     }
 }
 
@@ -87,14 +104,13 @@ pub enum Export {
     Named,
     Default,
 }
-impl Export {
-    pub fn to_string(self) -> String {
-        match self {
+impl fmt::Display for Export {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
             Export::Private => "",
             Export::Named => "export ",
             Export::Default => "export default ",
-        }
-        .to_string()
+        })
     }
 }
 
@@ -103,14 +119,13 @@ pub enum ClassType {
     Abstract,
     Normal,
 }
-impl ClassType {
-    pub fn to_string(self) -> String {
-        match self {
+impl fmt::Display for ClassType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
             ClassType::Interface => "interface",
             ClassType::Abstract => "abstract class",
             ClassType::Normal => "class",
-        }
-        .to_string()
+        })
     }
 }
 
@@ -119,6 +134,11 @@ pub struct Builder {
     level: u8,
     indent: String,
 }
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 impl Builder {
     pub fn new() -> Self {
         Builder {
@@ -141,6 +161,7 @@ impl Builder {
             indent: self.indent,
         }
     }
+    #[allow(clippy::should_implement_trait)]
     pub fn add<S>(self, str: S) -> Self
     where
         S: ToString,
@@ -164,6 +185,7 @@ impl Builder {
     }
 }
 
+#[derive(Default)]
 pub struct Script(Builder);
 impl Script {
     pub fn new() -> Self {
@@ -178,7 +200,7 @@ impl Script {
             self.0
                 .line()
                 .add(export)
-                .add(format!("{} ", abstraction.to_string()))
+                .add(format!("{} ", abstraction))
                 .add(str)
                 .add(" {")
                 .push(),
@@ -196,6 +218,66 @@ impl Script {
     pub fn import(self) -> Import {
         Import(self.0.line())
     }
+    /// Starts re-exporting names from another module.
+    pub fn export(self) -> Import {
+        Import(self.0.line().add("export "))
+    }
+    pub fn type_alias<S>(self, name: S, export: Export, kind: Type) -> Script
+    where
+        S: ToString,
+    {
+        Script(
+            self.0
+                .line()
+                .add(format!("{}type {} = {};", export, name.to_string(), kind)),
+        )
+    }
+    pub fn function<S>(self, name: S, export: Export) -> Method
+    where
+        S: ToString,
+    {
+        Method(
+            self.0
+                .line()
+                .add(format!("{}function {}(", export, name.to_string())),
+        )
+    }
+    pub fn declare_const<S>(self, name: S) -> Expression
+    where
+        S: ToString,
+    {
+        Expression(self.0.line().add(format!("const {} = ", name.to_string())))
+    }
+    pub fn block<S>(self, header: S) -> Script
+    where
+        S: ToString,
+    {
+        Script(self.0.line().add(header).add(" {").push())
+    }
+    pub fn block_end(self) -> Script {
+        Script(self.0.pop().line().add("}"))
+    }
+    /// Closes a block and opens the one following it, like `} catch {`.
+    pub fn block_next<S>(self, header: S) -> Script
+    where
+        S: ToString,
+    {
+        Script(self.0.pop().line().add("} ").add(header).add(" {").push())
+    }
+    pub fn case<S>(self, value: S) -> Script
+    where
+        S: ToString,
+    {
+        Script(
+            self.0
+                .line()
+                .add(format!("case {}:", value.to_string()))
+                .push(),
+        )
+    }
+    pub fn case_end(self) -> Script {
+        Script(self.0.pop())
+    }
 }
 
 pub struct Import(Builder);
@@ -204,19 +286,38 @@ impl Import {
     where
         S: ToString,
     {
-        Import(self.0.add("import ").add(name).add(" from \""))
+        Import(self.0.add("import ").add(name))
     }
     pub fn by_all<S>(self, alias: S) -> Import
     where
         S: ToString,
     {
-        Import(self.0.add("import * as ").add(alias).add(" from \""))
+        Import(self.0.add("import * as ").add(alias))
+    }
+    pub fn named<S>(self, names: &[S]) -> Import
+    where
+        S: ToString,
+    {
+        let names = names
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let clause = format!("{{ {} }}", names);
+        let current_line = self.0.output.rsplit('\n').next().unwrap_or_default();
+        Import(if current_line.starts_with("import") {
+            self.0.add(", ").add(clause)
+        } else if current_line.starts_with("export") {
+            self.0.add(clause)
+        } else {
+            self.0.add("import ").add(clause)
+        })
     }
     pub fn from<S>(self, path: S) -> Import
     where
         S: ToString,
     {
-        Import(self.0.add(path))
+        Import(self.0.add(" from \"").add(path))
     }
     pub fn import_end(self) -> Script {
         Script(self.0.add("\";"))
@@ -237,6 +338,27 @@ impl Expression {
     {
         Expression(self.0.add(name.to_string()))
     }
+    pub fn type_argument(self, kind: Type) -> Expression {
+        Expression(self.0.add(format!("<{}>", kind)))
+    }
+    pub fn array(self) -> ArrayExpression {
+        ArrayExpression(self.0.add("["))
+    }
+    pub fn object(self) -> ObjectExpression {
+        ObjectExpression(self.0.add("{ "))
+    }
+    pub fn item_end(self) -> ArrayExpression {
+        ArrayExpression(self.0)
+    }
+    pub fn key<S>(self, key: S) -> Expression
+    where
+        S: ToString,
+    {
+        Expression(self.0.add(format!("[\"{}\"]", key.to_string())))
+    }
+    pub fn index(self, index: usize) -> Expression {
+        Expression(self.0.add(format!("[{}]", index)))
+    }
     pub fn call(self) -> CallExpression {
         CallExpression(self.0.add("("))
     }
@@ -258,9 +380,24 @@ impl Expression {
     {
         Expression(self.0.add(value.to_string()))
     }
+    pub fn null(self) -> Expression {
+        Expression(self.0.add("null"))
+    }
+    pub fn coalesce(self) -> Expression {
+        Expression(self.0.add(" ?? "))
+    }
+    pub fn construct<S>(self, class: S) -> Expression
+    where
+        S: ToString,
+    {
+        Expression(self.0.add("new ").add(class))
+    }
     pub fn do_return(self) -> Expression {
         Expression(self.0.add("return "))
     }
+    pub fn do_throw(self) -> Expression {
+        Expression(self.0.add("throw "))
+    }
     pub fn do_await(self) -> Expression {
         Expression(self.0.add("await "))
     }
@@ -280,11 +417,90 @@ impl CallExpression {
     }
 }
 
+pub struct ArrayExpression(Builder);
+impl ArrayExpression {
+    pub fn item(self) -> Expression {
+        Expression(if self.0.output.ends_with('[') {
+            self.0
+        } else {
+            self.0.add(", ")
+        })
+    }
+    pub fn array_end(self) -> Expression {
+        Expression(self.0.add("]"))
+    }
+}
+
+pub struct ObjectExpression(Builder);
+impl ObjectExpression {
+    fn separate(self) -> Builder {
+        if self.0.output.ends_with("{ ") {
+            self.0
+        } else {
+            self.0.add(", ")
+        }
+    }
+    pub fn spread<S>(self, name: S) -> ObjectExpression
+    where
+        S: ToString,
+    {
+        ObjectExpression(self.separate().add("...").add(name))
+    }
+    pub fn shorthand<S>(self, name: S) -> ObjectExpression
+    where
+        S: ToString,
+    {
+        ObjectExpression(self.separate().add(name))
+    }
+    pub fn object_end(self) -> Expression {
+        Expression(self.0.add(" }"))
+    }
+}
+
 pub struct Class(Builder);
 impl Class {
     pub fn class_end(self) -> Script {
         Script(self.0.pop().line().add("}"))
     }
+    pub fn property<S>(self, name: S, kind: Type) -> Class
+    where
+        S: ToString,
+    {
+        Class(
+            self.0
+                .line()
+                .add(format!("{}: {};", name.to_string(), kind)),
+        )
+    }
+    pub fn optional_property<S>(self, name: S, kind: Type) -> Class
+    where
+        S: ToString,
+    {
+        self.property(format!("{}?", name.to_string()), kind)
+    }
+    pub fn static_readonly<S, V>(self, name: S, kind: Type, value: V) -> Class
+    where
+        S: ToString,
+        V: ToString,
+    {
+        Class(self.0.line().add(format!(
+            "public static readonly {}: {} = {};",
+            name.to_string(),
+            kind,
+            value.to_string()
+        )))
+    }
+    pub fn readonly<S, V>(self, name: S, value: V) -> Class
+    where
+        S: ToString,
+        V: ToString,
+    {
+        Class(self.0.line().add(format!(
+            "public readonly {} = {};",
+            name.to_string(),
+            value.to_string()
+        )))
+    }
     pub fn constructor(self) -> Method {
         Method(self.0.line().add("constructor("))
     }
@@ -293,12 +509,15 @@ impl Class {
         S: ToString,
     {
         let is_async = if is_async { "async " } else { "" };
-        Method(self.0.line().add(format!(
-            "{} {}{}(",
-            visibility.to_string(),
-            is_async,
-            name.to_string()
-        )))
+        let visibility = match visibility {
+            Visibility::NotSpecified => "".to_owned(),
+            visibility => format!("{} ", visibility),
+        };
+        Method(
+            self.0
+                .line()
+                .add(format!("{}{}{}(", visibility, is_async, name.to_string())),
+        )
     }
 }
 
@@ -314,6 +533,12 @@ impl Method {
             self.0.add(", ").add(name).add(": ").add(kind.to_string())
         })
     }
+    pub fn optional_param<S>(self, name: S, kind: Type) -> Self
+    where
+        S: ToString,
+    {
+        self.param(format!("{}?", name.to_string()), kind)
+    }
     pub fn rest_param(self, name: &str, kind: Type) -> Self {
         Method(if self.0.output.ends_with("(") {
             self.0.add("...").add(name).add(": ").add(kind.to_string())
@@ -330,19 +555,19 @@ impl Method {
         S: ToString,
     {
         let readonly = if readonly {
-            format!("readonly ")
+            "readonly ".to_owned()
         } else {
             "".to_owned()
         };
         Method(if self.0.output.ends_with("(") {
             self.0
-                .add(format!("{} {}", visibility.to_string(), readonly))
+                .add(format!("{} {}", visibility, readonly))
                 .add(name)
                 .add(": ")
                 .add(kind.to_string())
         } else {
             self.0
-                .add(format!(", {} {}", visibility.to_string(), readonly))
+                .add(format!(", {} {}", visibility, readonly))
                 .add(name)
                 .add(": ")
                 .add(kind.to_string())
@@ -352,7 +577,7 @@ impl Method {
         Class(self.0.add(") {}"))
     }
     pub fn method_end_abstract(self, return_type: Type) -> Class {
-        Class(self.0.add(format!("): {};", return_type.to_string())))
+        Class(self.0.add(format!("): {};", return_type)))
     }
     pub fn constructor_end(self) -> Class {
         self.method_end()
@@ -360,4 +585,7 @@ impl Method {
     pub fn body(self) -> Script {
         Script(self.0.add(") {").push())
     }
+    pub fn body_returning(self, return_type: Type) -> Script {
+        Script(self.0.add(format!("): {} {{", return_type)).push())
+    }
 }